@@ -0,0 +1,54 @@
+/// A font libharu has built in, which can be passed to `Document::get_font`.
+pub trait BuiltinFont {
+    fn font_name(&self) -> &'static str;
+}
+
+/// The standard 14 fonts every PDF viewer provides, so they never need to be embedded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BaseFont {
+    Courier,
+    CourierBold,
+    CourierOblique,
+    CourierBoldOblique,
+    Helvetica,
+    HelveticaBold,
+    HelveticaOblique,
+    HelveticaBoldOblique,
+    TimesRoman,
+    TimesBold,
+    TimesItalic,
+    TimesBoldItalic,
+    
+    /// Greek letters and mathematical symbols. Use with `Encoding::FontSpecific`.
+    Symbol,
+    
+    /// Dingbats. Use with `Encoding::FontSpecific`.
+    ZapfDingbats,
+}
+
+impl BaseFont {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            BaseFont::Courier => "Courier",
+            BaseFont::CourierBold => "Courier-Bold",
+            BaseFont::CourierOblique => "Courier-Oblique",
+            BaseFont::CourierBoldOblique => "Courier-BoldOblique",
+            BaseFont::Helvetica => "Helvetica",
+            BaseFont::HelveticaBold => "Helvetica-Bold",
+            BaseFont::HelveticaOblique => "Helvetica-Oblique",
+            BaseFont::HelveticaBoldOblique => "Helvetica-BoldOblique",
+            BaseFont::TimesRoman => "Times-Roman",
+            BaseFont::TimesBold => "Times-Bold",
+            BaseFont::TimesItalic => "Times-Italic",
+            BaseFont::TimesBoldItalic => "Times-BoldItalic",
+            BaseFont::Symbol => "Symbol",
+            BaseFont::ZapfDingbats => "ZapfDingbats",
+        }
+    }
+}
+
+impl BuiltinFont for BaseFont {
+    fn font_name(&self) -> &'static str {
+        self.name()
+    }
+}
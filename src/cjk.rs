@@ -0,0 +1,76 @@
+use base_font::BuiltinFont;
+
+/// The families of Chinese, Japanese and Korean fonts and encodings libharu has built in.
+/// Each must be enabled on a `Document` before its fonts or encodings can be used.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CjkLanguage {
+    Japanese,
+    Korean,
+    SimplifiedChinese,
+    TraditionalChinese,
+}
+
+/// The CID fonts libharu has built in. Like the standard 14 fonts, these are never embedded.
+///
+/// Each needs `Document::use_cjk_fonts` for its language, and one of that language's encodings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CidFont {
+    /// Japanese
+    MsMincho,
+    /// Japanese
+    MsPMincho,
+    /// Japanese
+    MsGothic,
+    /// Japanese
+    MsPGothic,
+    
+    /// Korean
+    Batang,
+    /// Korean
+    BatangChe,
+    /// Korean
+    Dotum,
+    /// Korean
+    DotumChe,
+    
+    /// Simplified Chinese
+    SimSun,
+    /// Simplified Chinese
+    SimHei,
+    
+    /// Traditional Chinese
+    MingLiU,
+}
+
+impl CidFont {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            CidFont::MsMincho => "MS-Mincho",
+            CidFont::MsPMincho => "MS-PMincho",
+            CidFont::MsGothic => "MS-Gothic",
+            CidFont::MsPGothic => "MS-PGothic",
+            CidFont::Batang => "Batang",
+            CidFont::BatangChe => "BatangChe",
+            CidFont::Dotum => "Dotum",
+            CidFont::DotumChe => "DotumChe",
+            CidFont::SimSun => "SimSun",
+            CidFont::SimHei => "SimHei",
+            CidFont::MingLiU => "MingLiU",
+        }
+    }
+    
+    pub fn language(&self) -> CjkLanguage {
+        match *self {
+            CidFont::MsMincho | CidFont::MsPMincho | CidFont::MsGothic | CidFont::MsPGothic => CjkLanguage::Japanese,
+            CidFont::Batang | CidFont::BatangChe | CidFont::Dotum | CidFont::DotumChe => CjkLanguage::Korean,
+            CidFont::SimSun | CidFont::SimHei => CjkLanguage::SimplifiedChinese,
+            CidFont::MingLiU => CjkLanguage::TraditionalChinese,
+        }
+    }
+}
+
+impl BuiltinFont for CidFont {
+    fn font_name(&self) -> &'static str {
+        self.name()
+    }
+}
@@ -0,0 +1,36 @@
+use libharu_sys as haru;
+
+/// Which kinds of streams are compressed with Flate when the document is saved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Compression {
+    /// Page contents.
+    pub text: bool,
+
+    /// Images other than JPEGs, which are already compressed.
+    pub image: bool,
+
+    /// Other streams, such as embedded fonts and CMaps.
+    pub metadata: bool,
+}
+
+impl Compression {
+    pub fn all() -> Compression {
+        Compression{ text: true, image: true, metadata: true }
+    }
+
+    pub fn none() -> Compression {
+        Compression{ text: false, image: false, metadata: false }
+    }
+
+    pub fn is_none(&self) -> bool {
+        !self.text && !self.image && !self.metadata
+    }
+
+    pub fn as_int(&self) -> haru::HPDF_UINT {
+        let mut mode = haru::HPDF_COMP_NONE;
+        if self.text { mode |= haru::HPDF_COMP_TEXT; }
+        if self.image { mode |= haru::HPDF_COMP_IMAGE; }
+        if self.metadata { mode |= haru::HPDF_COMP_METADATA; }
+        mode as haru::HPDF_UINT
+    }
+}
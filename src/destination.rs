@@ -0,0 +1,93 @@
+use libharu_sys as haru;
+use document_inner::DocumentInner;
+use std::rc::Rc;
+
+use error::{Code, Error, Result};
+use page::{Page, Rect};
+
+/// A view of a page, which outlines and links can go to.
+#[derive(Clone)]
+pub struct Destination {
+    handle: haru::HPDF_Destination,
+    doc: Rc<DocumentInner>
+}
+
+impl Destination {
+    pub fn from_handle(handle: haru::HPDF_Destination, doc: Rc<DocumentInner>) -> Destination {
+        Destination{ handle: handle, doc: doc }
+    }
+    
+    pub unsafe fn get_handle(&self) -> haru::HPDF_Destination {
+        self.handle
+    }
+    
+    /// Shows `page` with `(left, top)` at the upper left corner of the window, magnified by `zoom`,
+    /// where 1 is 100%. See `set_xyz` for the allowed values.
+    pub fn xyz(page: &Page, left: f32, top: f32, zoom: f32) -> Result<Destination> {
+        let mut dest = try!(page.create_destination());
+        try!(dest.set_xyz(left, top, zoom));
+        Ok(dest)
+    }
+    
+    /// Shows all of `page`.
+    pub fn fit(page: &Page) -> Result<Destination> {
+        page.create_destination()
+    }
+    
+    /// Shows the full width of `page`, with `top` at the top of the window.
+    pub fn fit_h(page: &Page, top: f32) -> Result<Destination> {
+        let mut dest = try!(page.create_destination());
+        try!(dest.set_fit_h(top));
+        Ok(dest)
+    }
+    
+    /// Shows the full height of `page`, with `left` at the left edge of the window.
+    pub fn fit_v(page: &Page, left: f32) -> Result<Destination> {
+        let mut dest = try!(page.create_destination());
+        try!(dest.set_fit_v(left));
+        Ok(dest)
+    }
+    
+    /// Shows `rect` of `page` as large as it fits.
+    pub fn fit_r(page: &Page, rect: Rect) -> Result<Destination> {
+        let mut dest = try!(page.create_destination());
+        try!(dest.set_fit_r(rect));
+        Ok(dest)
+    }
+    
+    /// Shows the bounding box of `page`'s contents.
+    pub fn fit_b(page: &Page) -> Result<Destination> {
+        let mut dest = try!(page.create_destination());
+        try!(dest.set_fit_b());
+        Ok(dest)
+    }
+    
+    /// `left` and `top` must not be negative, and `zoom` must be from 0.08 to 32 (8% to 3200%).
+    /// Other values fail with `Code::InvalidParameter`.
+    pub fn set_xyz(&mut self, left: f32, top: f32, zoom: f32) -> Result<()> {
+        if !(left >= 0.0 && top >= 0.0 && zoom >= 0.08 && zoom <= 32.0) {
+            return Error::new_err(Code::InvalidParameter);
+        }
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Destination_SetXYZ(self.handle, left, top, zoom) } )
+    }
+    
+    pub fn set_fit(&mut self) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Destination_SetFit(self.handle) } )
+    }
+    
+    pub fn set_fit_h(&mut self, top: f32) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Destination_SetFitH(self.handle, top) } )
+    }
+    
+    pub fn set_fit_v(&mut self, left: f32) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Destination_SetFitV(self.handle, left) } )
+    }
+    
+    pub fn set_fit_r(&mut self, rect: Rect) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Destination_SetFitR(self.handle, rect.left, rect.bottom, rect.right, rect.top) } )
+    }
+    
+    pub fn set_fit_b(&mut self) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Destination_SetFitB(self.handle) } )
+    }
+}
@@ -1,16 +1,30 @@
 
 use libharu_sys as haru;
 use std::ptr;
-use std::io::{Read, Write, Seek, SeekFrom};
-use std::mem::{transmute, forget};
+use std::io::{self, Cursor, Read, Write, Seek};
+use std::mem::transmute;
 use std::slice;
 use std::boxed::Box;
+use std::ffi::{CStr, CString};
+use std::path::Path;
 
-use document_inner::DocumentInner;
+use base_font::BuiltinFont;
+use cjk::CjkLanguage;
+use compression::Compression;
+use destination::Destination;
+use document_inner::{DocumentInner, ErrorState, error_handler};
+use encoding::Encoding;
+use encryption::{EncryptionMode, Permissions};
 use error::{Code, Error, Result};
 use font::Font;
+use image::{ColorSpace, Image, raw_image_len, split_alpha};
+use info::{Date, InfoAttribute};
+use outline::Outline;
 use page::Page;
 use page_layout::PageLayout;
+use page_mode::PageMode;
+use reader_stream::ReaderStream;
+use std::cell::RefCell;
 use std::ops::DerefMut;
 use std::rc::Rc;
 
@@ -20,208 +34,489 @@ pub struct Document {
 
 impl Document {
     pub fn new() -> Result<Document> {
-        let handle = try!(Error::check_non_null(unsafe { haru::HPDF_New(None, ptr::null_mut()) }));
-        try!( Error::from_status(unsafe { haru::HPDF_UseUTFEncodings(handle) } ) );
+        let mut errors = Box::new(ErrorState::new());
+        let user_data = errors.deref_mut() as *mut ErrorState as *mut ::libc::c_void;
+        let handle = try!(Error::check_non_null(unsafe { haru::HPDF_New(Some(error_handler), user_data) }));
         
-        Ok(Document {
-            inner: Rc::new(DocumentInner{handle: handle})
-        })
+        let doc = Document {
+            inner: Rc::new(DocumentInner{handle: handle, errors: errors, font_stream_errors: RefCell::new(Vec::new())})
+        };
+        try!( Error::from_doc_status(handle, unsafe { haru::HPDF_UseUTFEncodings(handle) } ) );
         
+        Ok(doc)
+    }
+    
+    /// Registers a function to be called whenever libharu raises an error on this document,
+    /// at the point of failure. Useful for logging.
+    pub fn on_error<F: Fn(&Error) + 'static>(&self, hook: F) {
+        self.inner.errors.hooks.borrow_mut().push(Box::new(hook));
+    }
+    
+    /// The most recent error raised on this document, if it has not been cleared by `reset_error`.
+    pub fn last_error(&self) -> Option<Error> {
+        if unsafe { haru::HPDF_GetError(self.inner.handle) } == 0 {
+            return None;
+        }
+        self.inner.errors.last.get().map(|(error_no, detail_no)| Error::with_detail(error_no, detail_no))
+    }
+    
+    /// Clears the document's error state. libharu refuses most operations on a document
+    /// whose error has not been cleared.
+    pub fn reset_error(&self) {
+        unsafe { haru::HPDF_ResetError(self.inner.handle); }
+        self.inner.errors.last.set(None);
+    }
+    
+    /// Makes the built-in `CidFont`s for a language available to `get_font`.
+    pub fn use_cjk_fonts(&mut self, language: CjkLanguage) -> Result<()> {
+        let status = unsafe {
+            match language {
+                CjkLanguage::Japanese => haru::HPDF_UseJPFonts(self.inner.handle),
+                CjkLanguage::Korean => haru::HPDF_UseKRFonts(self.inner.handle),
+                CjkLanguage::SimplifiedChinese => haru::HPDF_UseCNSFonts(self.inner.handle),
+                CjkLanguage::TraditionalChinese => haru::HPDF_UseCNTFonts(self.inner.handle),
+            }
+        };
+        Error::from_doc_status(self.inner.handle, status)
+    }
+    
+    /// Makes the CMap encodings for a language available to `get_font`.
+    pub fn use_cjk_encodings(&mut self, language: CjkLanguage) -> Result<()> {
+        let status = unsafe {
+            match language {
+                CjkLanguage::Japanese => haru::HPDF_UseJPEncodings(self.inner.handle),
+                CjkLanguage::Korean => haru::HPDF_UseKREncodings(self.inner.handle),
+                CjkLanguage::SimplifiedChinese => haru::HPDF_UseCNSEncodings(self.inner.handle),
+                CjkLanguage::TraditionalChinese => haru::HPDF_UseCNTEncodings(self.inner.handle),
+            }
+        };
+        Error::from_doc_status(self.inner.handle, status)
     }
     
     pub fn set_pages_configuration(&mut self, page_per_pages: u32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_SetPagesConfiguration(self.inner.handle, page_per_pages ) })
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetPagesConfiguration(self.inner.handle, page_per_pages ) })
     }
     
     pub fn set_page_layout(&mut self, layout: PageLayout) -> Result<()> {
         let layout_code = layout.as_int();
-        Error::from_status( unsafe { haru::HPDF_SetPageLayout(self.inner.handle, layout_code ) })
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetPageLayout(self.inner.handle, layout_code ) })
     }
     
     pub fn get_page_layout(&self) -> Option<PageLayout> {
         PageLayout::from_int( unsafe { haru::HPDF_GetPageLayout(self.inner.handle) } )
     }
     
+    pub fn set_page_mode(&mut self, mode: PageMode) -> Result<()> {
+        let mode_code = mode.as_int();
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetPageMode(self.inner.handle, mode_code ) })
+    }
+    
+    pub fn get_page_mode(&self) -> Option<PageMode> {
+        PageMode::from_int( unsafe { haru::HPDF_GetPageMode(self.inner.handle) } )
+    }
+    
+    /// Where the viewer goes when the document is opened.
+    pub fn set_open_action(&mut self, destination: &Destination) -> Result<()> {
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetOpenAction(self.inner.handle, destination.get_handle()) })
+    }
+    
+    /// Encrypts the document. Opening it requires either password; the owner password also lifts the
+    /// restrictions set with `set_permission`. The user password may be empty, so that anyone can open
+    /// the document, but the owner password must not be, and the two must differ.
+    ///
+    /// This must be called before `set_permission` and `set_encryption_mode`.
+    pub fn set_password(&mut self, owner_password: &str, user_password: &str) -> Result<()> {
+        let owner = try!(CString::new(owner_password));
+        let user = try!(CString::new(user_password));
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetPassword(self.inner.handle, owner.as_ptr(), user.as_ptr()) })
+    }
+    
+    pub fn set_permission(&mut self, permissions: Permissions) -> Result<()> {
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetPermission(self.inner.handle, permissions.bits()) })
+    }
+    
+    /// Chooses the encryption algorithm. Without this, `Rc4_40` is used.
+    pub fn set_encryption_mode(&mut self, mode: EncryptionMode) -> Result<()> {
+        if !mode.is_valid() {
+            return Error::new_err(Code::InvalidEncryptKeyLen);
+        }
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetEncryptionMode(self.inner.handle, mode.as_int(), mode.key_len()) })
+    }
+    
+    /// Chooses which streams to compress. libharu must have been built with zlib to compress anything;
+    /// without the `zlib` feature, asking for compression fails with `Code::InvalidCompressionMode`.
+    pub fn set_compression(&mut self, compression: Compression) -> Result<()> {
+        if !cfg!(feature = "zlib") && !compression.is_none() {
+            return Error::new_err(Code::InvalidCompressionMode);
+        }
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetCompressionMode(self.inner.handle, compression.as_int()) })
+    }
+    
+    /// Sets a text attribute of the document information dictionary, which viewers show as the
+    /// document's properties.
+    ///
+    /// libharu encodes the value with the document's current encoder, so this makes UTF-8 the current
+    /// encoder and leaves it that way.
+    pub fn set_info(&mut self, attribute: InfoAttribute, value: &str) -> Result<()> {
+        let value = try!(CString::new(value));
+        let encoder = try!(CString::new(Encoding::Utf8.name()));
+        try!(Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetCurrentEncoder(self.inner.handle, encoder.as_ptr()) }));
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetInfoAttr(self.inner.handle, attribute.as_int(), value.as_ptr()) })
+    }
+    
+    /// Gets a text attribute of the document information dictionary, if it has been set.
+    pub fn info(&self, attribute: InfoAttribute) -> Option<String> {
+        let value = unsafe { haru::HPDF_GetInfoAttr(self.inner.handle, attribute.as_int()) };
+        if value.is_null() {
+            return None;
+        }
+        Some( unsafe { CStr::from_ptr(value) }.to_string_lossy().into_owned() )
+    }
+    
+    pub fn set_creation_date(&mut self, date: Date) -> Result<()> {
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetInfoDateAttr(self.inner.handle, haru::HPDF_INFO_CREATION_DATE, date.to_haru()) })
+    }
+    
+    pub fn set_modification_date(&mut self, date: Date) -> Result<()> {
+        Error::from_doc_status(self.inner.handle, unsafe { haru::HPDF_SetInfoDateAttr(self.inner.handle, haru::HPDF_INFO_MOD_DATE, date.to_haru()) })
+    }
+    
+    pub fn creation_date(&self) -> Option<Date> {
+        self.info_date(haru::HPDF_INFO_CREATION_DATE)
+    }
+    
+    pub fn modification_date(&self) -> Option<Date> {
+        self.info_date(haru::HPDF_INFO_MOD_DATE)
+    }
+    
+    fn info_date(&self, info_type: haru::HPDF_InfoType) -> Option<Date> {
+        let value = unsafe { haru::HPDF_GetInfoAttr(self.inner.handle, info_type) };
+        if value.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(value) }.to_str().ok().and_then(Date::parse)
+    }
+    
+    /// Adds an entry to the outline, under `parent` or at the top level. The title is written with
+    /// `encoding`, which must be `Encoding::Utf8` unless the title is plain ASCII.
+    pub fn create_outline(&self, parent: Option<&Outline>, title: &str, encoding: Encoding) -> Result<Outline> {
+        if encoding != Encoding::Utf8 && !title.is_ascii() {
+            return Error::new_err(Code::InvalidEncoder);
+        }
+        let title = try!(CString::new(title));
+        let encoding_name = try!(CString::new(encoding.name()));
+        let encoder = unsafe { haru::HPDF_GetEncoder(self.inner.handle, encoding_name.as_ptr()) };
+        if encoder.is_null() {
+            return Error::last_err(self.inner.handle);
+        }
+        
+        let parent = match parent {
+            Some(parent) => unsafe { parent.get_handle() },
+            None => ptr::null_mut(),
+        };
+        let outline = unsafe { haru::HPDF_CreateOutline(self.inner.handle, parent, title.as_ptr(), encoder) };
+        if outline.is_null() {
+            return Error::last_err(self.inner.handle);
+        }
+        Ok(Outline::from_handle(outline, self.inner.clone()))
+    }
+    
     pub fn add_page(&self) -> Result<Page> {
-        let page = try!(Error::check_non_null(unsafe { haru::HPDF_AddPage(self.inner.handle) }));
+        let page = unsafe { haru::HPDF_AddPage(self.inner.handle) };
+        if page.is_null() {
+            return Error::last_err(self.inner.handle);
+        }
         
         Ok(Page::from_handle(page, self.inner.clone()))
     }
     
     pub fn insert_page(&self, target: Page) -> Result<Page> {
-        let page = try!(Error::check_non_null(unsafe { haru::HPDF_InsertPage(self.inner.handle, target.get_handle()) }));
+        let page = unsafe { haru::HPDF_InsertPage(self.inner.handle, target.get_handle()) };
+        if page.is_null() {
+            return Error::last_err(self.inner.handle);
+        }
         
         Ok(Page::from_handle(page, self.inner.clone()))
     }
     
-    /*pub fn get_font(&self, name: &str) -> Result<Font> {
-        let chrs = try!(CString::new(name));
-        let encoder_name = try!(CString::new("UTF-8"));
-        
-        let encoder = unsafe { haru::HPDF_GetEncoder(self.inner.handle, encoder_name.as_ptr()) };
-        println!("Encoder = {:?} {}", encoder, encoder==ptr::null_mut());
-        
-        let font_handle = unsafe { haru::HPDF_GetFont(self.inner.handle, chrs.as_ptr(), encoder_name.as_ptr()) };
+    /// Gets one of the fonts libharu has built in: a `BaseFont`, or a `CidFont` once its language
+    /// has been enabled with `use_cjk_fonts`. These are never embedded.
+    pub fn get_font<F: BuiltinFont>(&self, font: F, encoding: Encoding) -> Result<Font> {
+        let font_name = try!(CString::new(font.font_name()));
+        self.get_font_by_name(font_name.as_ptr(), encoding)
+    }
+    
+    fn get_font_by_name(&self, font_name: *const ::libc::c_char, encoding: Encoding) -> Result<Font> {
+        let encoding_name = try!(CString::new(encoding.name()));
+        let font_handle = unsafe { haru::HPDF_GetFont(self.inner.handle, font_name, encoding_name.as_ptr()) };
         if font_handle == ptr::null_mut() {
-            println!("It returned null");
-            try!(Error::from_status( unsafe { haru::HPDF_GetError(self.inner.handle) } ) );
-            return Error::new_err(Code::Unknown);
+            return Error::last_err(self.inner.handle);
         }
         
-        Ok(Font::from_handle(font_handle))
-    }*/
+        Ok(Font::from_handle(font_handle, self.inner.clone()))
+    }
     
-    pub fn get_ttf_font<R: Read+Seek>(&self, r: R) -> Result<Font> {
-        extern "C" fn read<R: Read+Seek>(stream: haru::HPDF_Stream, ptr: *mut haru::HPDF_BYTE, size: *mut haru::HPDF_UINT) -> haru::HPDF_STATUS {
-            let r : &mut TellingReader<R> = unsafe { transmute( (*stream).attr ) };
-            let buf: &mut [u8] = unsafe { slice::from_raw_parts_mut(ptr, *size as usize) };
-            
-            let mut read_total = 0;
-            while read_total < buf.len() {
-                let read_len = match r.reader.read(&mut buf[read_total..]) {
-                    Ok(0) => { 
-                        unsafe { *size = read_total as haru::HPDF_UINT };
-                        println!("Read to EOF");
-                        return 0x1058 /* EOF */; 
-                    }
-                    Ok(read_len) => read_len,
-                    Err(_) => { 
-                        unsafe { *size = 0; }
-                        return 0x1016; 
-                    },
-                };
-                
-                read_total += read_len;
-            }
-            
-            r.pos += read_total as u64;
-            
-            return 0;
-        }
+    /// Loads and embeds a TrueType font. Equivalent to `load_ttf_font(r, true)`.
+    pub fn get_ttf_font<R: Read+Seek+'static>(&self, r: R) -> Result<Font> {
+        self.load_ttf_font(r, true)
+    }
+    
+    /// Loads a TrueType font from a reader. When `embed` is false, only the font's metrics go into the
+    /// document and viewers must have the font installed to display it.
+    pub fn load_ttf_font<R: Read+Seek+'static>(&self, r: R, embed: bool) -> Result<Font> {
+        let stream = ReaderStream::new(self.inner.handle, r);
         
-        extern "C" fn seek<R: Read+Seek>(stream: haru::HPDF_Stream, pos: haru::HPDF_INT, mode: haru::HPDF_WhenceMode) -> haru::HPDF_STATUS {
-            let r : &mut TellingReader<R> = unsafe { transmute( (*stream).attr ) };
-            
-            let res = r.reader.seek(match mode {
-                haru::HPDF_SEEK_CUR => SeekFrom::Current(pos as i64),
-                haru::HPDF_SEEK_END => SeekFrom::End(pos as i64),
-                haru::HPDF_SEEK_SET | _ => SeekFrom::Start(pos as u64),
-            });
-            
-            if let Ok(sought_to) = res {
-                r.pos = sought_to;
-                0
-            } else {
-                0x1016
-            }
-        }
-        extern "C" fn tell<R: Read+Seek>(stream: haru::HPDF_Stream) -> haru::HPDF_INT32 {
-            let r : &mut TellingReader<R> = unsafe { transmute( (*stream).attr ) };
-            r.pos as haru::HPDF_INT32
-        }
-        extern "C" fn size<R: Read+Seek>(stream: haru::HPDF_Stream) -> haru::HPDF_UINT32 {
-            let r : &mut TellingReader<R> = unsafe { transmute( (*stream).attr ) };
-            let saved_pos = r.pos;
-            let ret = r.reader.seek(SeekFrom::End(0)).ok().unwrap_or(0);
-            if r.reader.seek(SeekFrom::Start(saved_pos)).is_err() {
-                // Oops -- we failed to seek back to where we started.
-                return 0;
-            }
-            ret as haru::HPDF_UINT32
-        }
-        extern "C" fn free<R: Read+Seek>(stream: haru::HPDF_Stream) {
-            let r : Box<TellingReader<R>> = unsafe { transmute( (*stream).attr ) };
-            drop(r)
-        }
+        haru_debug!("loading TrueType font from stream");
         
-        struct TellingReader<R: Read+Seek> {
-            reader: R,
-            pos: u64
-        }
-        impl<R: Read+Seek> Drop for TellingReader<R> {
-            fn drop(&mut self) {
-                println!("Dropping the TellingReader");
+        // The font takes ownership of the stream
+        let name = unsafe { haru::HPDF_LoadTTFontFromStream(self.inner.handle, stream.handle, embed as haru::HPDF_BOOL, ptr::null()) };
+        
+        if name == ptr::null() {
+            haru_debug!("HPDF_LoadTTFontFromStream failed");
+            if let Some(e) = stream.take_error() {
+                return Err(Error::from(e));
             }
+            return Error::last_err(self.inner.handle);
         }
         
-        let mmgr = unsafe { haru::HPDF_GetMMgr(self.inner.handle) };
+        haru_debug!("loaded TrueType font {:?}", unsafe { CStr::from_ptr(name) });
         
-        let mut tr = Box::new(TellingReader{
-            reader: r,
-            pos: 0
-        });
-        
-        let stream = unsafe { haru::HPDF_CallbackReader_New(
-            mmgr, 
-            Some(read::<R>),
-            Some(seek::<R>),
-            Some(tell::<R>),
-            Some(size::<R>),
-            Some(free::<R>),
-            tr.deref_mut() as *mut TellingReader<R> as *mut ::libc::c_void
-        ) };
+        self.inner.font_stream_errors.borrow_mut().push(stream.error_cell());
         
-        forget(tr);
+        self.get_font_by_name(name, Encoding::Utf8)
+    }
+    
+    /// Loads a TrueType font from an in-memory copy of the font file.
+    pub fn load_ttf_font_from_bytes<B: Into<Vec<u8>>>(&self, bytes: B, embed: bool) -> Result<Font> {
+        self.load_ttf_font(Cursor::new(bytes.into()), embed)
+    }
+    
+    /// Loads a TrueType font from a file. The file is read again when the document is saved if `embed` is true.
+    pub fn load_ttf_font_from_path(&self, path: &Path, embed: bool) -> Result<Font> {
+        let path_buf = try!(path_to_cstring(path));
         
-        // The font takes ownership of the stream
-        let name = unsafe { haru::HPDF_LoadTTFontFromStream(self.inner.handle, stream, 1, ptr::null()) };
+        haru_debug!("loading TrueType font from {}", path.display());
         
+        let name = unsafe { haru::HPDF_LoadTTFontFromFile(self.inner.handle, path_buf.as_ptr(), embed as haru::HPDF_BOOL) };
         if name == ptr::null() {
-            println!("No name");
-            try!(Error::from_status( unsafe { haru::HPDF_GetError(self.inner.handle) } ) );
-            return Error::new_err(Code::Unknown);
+            return Error::last_err(self.inner.handle);
         }
         
-        let utf8 = b"UTF-8\0";
-        let font_handle = unsafe { haru::HPDF_GetFont(self.inner.handle, name, utf8.as_ptr() as *const i8) };
-        if font_handle == ptr::null_mut() {
-            try!(Error::from_status( unsafe { haru::HPDF_GetError(self.inner.handle) } ) );
-            return Error::new_err(Code::Unknown);
+        self.get_font_by_name(name, Encoding::Utf8)
+    }
+    
+    /// Loads one face of a TrueType Collection (.ttc) file. `list_ttc_faces` tells which index is which.
+    pub fn load_ttc_font(&self, path: &Path, index: u32, embed: bool) -> Result<Font> {
+        let path_buf = try!(path_to_cstring(path));
+        
+        haru_debug!("loading face {} of TrueType collection {}", index, path.display());
+        
+        let name = unsafe { haru::HPDF_LoadTTFontFromFile2(self.inner.handle, path_buf.as_ptr(), index, embed as haru::HPDF_BOOL) };
+        if name == ptr::null() {
+            return Error::last_err(self.inner.handle);
         }
         
-        Ok(Font::from_handle(font_handle, self.inner.clone()))
+        self.get_font_by_name(name, Encoding::Utf8)
+    }
+
+    /// Loads a Type 1 font from its AFM metrics alone. The font is not embedded, so viewers must
+    /// have it installed; use `load_type1_font_with_data` to embed it.
+    /// Type 1 fonts carry their own glyph names, so `encoding` is usually `Encoding::Standard`,
+    /// `Encoding::WinAnsi` or `Encoding::FontSpecific`.
+    pub fn load_type1_font<A: Read>(&self, mut afm: A, encoding: Encoding) -> Result<Font> {
+        let mut afm_buf = Vec::new();
+        try!(afm.read_to_end(&mut afm_buf));
+        self.load_type1_font_from_bufs(afm_buf, None, encoding)
+    }
+    
+    /// Loads and embeds a Type 1 font from its AFM metrics and its PFB or PFA glyph data.
+    /// See `load_type1_font`.
+    pub fn load_type1_font_with_data<A: Read, D: Read>(&self, mut afm: A, mut data: D, encoding: Encoding) -> Result<Font> {
+        let mut afm_buf = Vec::new();
+        try!(afm.read_to_end(&mut afm_buf));
+        let mut data_buf = Vec::new();
+        try!(data.read_to_end(&mut data_buf));
+        self.load_type1_font_from_bufs(afm_buf, Some(data_buf), encoding)
     }
     
-    /*pub fn get_ttf_font(&self, file_name: &str) -> Result<Font> {
-        let file_name_buf = try!(CString::new(file_name));
-        let name = unsafe { haru::HPDF_LoadTTFontFromFile(self.inner.handle, file_name_buf.as_ptr(), 1) };
+    fn load_type1_font_from_bufs(&self, afm_buf: Vec<u8>, data_buf: Option<Vec<u8>>, encoding: Encoding) -> Result<Font> {
+        let afm_stream = ReaderStream::new(self.inner.handle, Cursor::new(afm_buf));
+        let data_stream = data_buf.map(|buf| ReaderStream::new(self.inner.handle, Cursor::new(buf)));
+        
+        haru_debug!("loading Type 1 font from stream");
+        
+        // libharu copies what it needs out of the streams, so they are ours to free
+        let name = unsafe { haru::HPDF_LoadType1FontFromStream(
+            self.inner.handle,
+            afm_stream.handle,
+            data_stream.as_ref().map(|stream| stream.handle).unwrap_or(ptr::null_mut())
+        ) };
+        afm_stream.free();
+        if let Some(data_stream) = data_stream {
+            data_stream.free();
+        }
         if name == ptr::null() {
-            println!("HPDF_LoadTTFontFromFile null");
-            try!(Error::from_status( unsafe { haru::HPDF_GetError(self.inner.handle) } ) );
-            return Error::new_err(Code::Unknown);
+            return Error::last_err(self.inner.handle);
         }
         
-        let encoder_name = try!(CString::new("UTF-8"));
+        haru_debug!("loaded Type 1 font {:?}", unsafe { CStr::from_ptr(name) });
         
-        let encoder = unsafe { haru::HPDF_GetEncoder(self.inner.handle, encoder_name.as_ptr()) };
-        println!("Encoder = {:?} {}", encoder, encoder==ptr::null_mut());
+        self.get_font_by_name(name, encoding)
+    }
+    
+    /// Loads a Type 1 font from an AFM file and, optionally, a PFB or PFA file to embed. See `load_type1_font`.
+    pub fn load_type1_font_from_path(&self, afm: &Path, data: Option<&Path>, encoding: Encoding) -> Result<Font> {
+        let afm_buf = try!(path_to_cstring(afm));
+        let data_buf = match data {
+            Some(data) => Some(try!(path_to_cstring(data))),
+            None => None,
+        };
         
-        let font_handle = unsafe { haru::HPDF_GetFont(self.inner.handle, name, encoder_name.as_ptr()) };
-        if font_handle == ptr::null_mut() {
-            println!("HPDF_GetFont returned null");
-            try!(Error::from_status( unsafe { haru::HPDF_GetError(self.inner.handle) } ) );
-            return Error::new_err(Code::Unknown);
+        haru_debug!("loading Type 1 font from {}", afm.display());
+        
+        let name = unsafe { haru::HPDF_LoadType1FontFromFile(
+            self.inner.handle,
+            afm_buf.as_ptr(),
+            data_buf.as_ref().map(|buf| buf.as_ptr()).unwrap_or(ptr::null())
+        ) };
+        if name == ptr::null() {
+            return Error::last_err(self.inner.handle);
         }
         
-        Ok(Font::from_handle(font_handle))
-    }*/
+        self.get_font_by_name(name, encoding)
+    }
 
+    /// Loads a PNG image. Transparency in the image is kept as a soft mask.
+    pub fn load_png<R: Read>(&self, mut r: R) -> Result<Image> {
+        let mut buf = Vec::new();
+        try!(r.read_to_end(&mut buf));
+        self.load_png_from_bytes(&buf)
+    }
+    
+    pub fn load_png_from_bytes(&self, bytes: &[u8]) -> Result<Image> {
+        let image = unsafe { haru::HPDF_LoadPngImageFromMem(self.inner.handle, bytes.as_ptr(), bytes.len() as haru::HPDF_UINT) };
+        self.image_from_handle(image)
+    }
+    
+    pub fn load_png_from_path(&self, path: &Path) -> Result<Image> {
+        let path_buf = try!(path_to_cstring(path));
+        
+        haru_debug!("loading PNG image from {}", path.display());
+        
+        let image = unsafe { haru::HPDF_LoadPngImageFromFile(self.inner.handle, path_buf.as_ptr()) };
+        self.image_from_handle(image)
+    }
+    
+    /// Loads a JPEG image. The compressed data is embedded as is.
+    pub fn load_jpeg<R: Read>(&self, mut r: R) -> Result<Image> {
+        let mut buf = Vec::new();
+        try!(r.read_to_end(&mut buf));
+        self.load_jpeg_from_bytes(&buf)
+    }
+    
+    pub fn load_jpeg_from_bytes(&self, bytes: &[u8]) -> Result<Image> {
+        let image = unsafe { haru::HPDF_LoadJpegImageFromMem(self.inner.handle, bytes.as_ptr(), bytes.len() as haru::HPDF_UINT) };
+        self.image_from_handle(image)
+    }
+    
+    pub fn load_jpeg_from_path(&self, path: &Path) -> Result<Image> {
+        let path_buf = try!(path_to_cstring(path));
+        
+        haru_debug!("loading JPEG image from {}", path.display());
+        
+        let image = unsafe { haru::HPDF_LoadJpegImageFromFile(self.inner.handle, path_buf.as_ptr()) };
+        self.image_from_handle(image)
+    }
+    
+    /// Loads uncompressed pixels, packed row by row from the top. `color_space` must be one of the
+    /// device color spaces. libharu does not pad rows, so each row must fill a whole number of bytes.
+    pub fn load_raw_image(&self, pixels: &[u8], width: u32, height: u32, color_space: ColorSpace, bits_per_component: u32) -> Result<Image> {
+        let len = try!(raw_image_len(width, height, color_space, bits_per_component));
+        if pixels.len() < len {
+            return Error::new_err(Code::InvalidImage);
+        }
+        
+        let image = unsafe { haru::HPDF_LoadRawImageFromMem(self.inner.handle, pixels.as_ptr(), width, height,
+            color_space as haru::HPDF_ColorSpace, bits_per_component) };
+        self.image_from_handle(image)
+    }
+    
+    /// Loads 8-bit RGBA pixels as an RGB image with the alpha channel as its soft mask.
+    pub fn load_rgba_image(&self, pixels: &[u8], width: u32, height: u32) -> Result<Image> {
+        let (rgb, alpha) = split_alpha(pixels);
+        let mut image = try!(self.load_raw_image(&rgb, width, height, ColorSpace::DeviceRgb, 8));
+        let mask = try!(self.load_raw_image(&alpha, width, height, ColorSpace::DeviceGray, 8));
+        try!(image.add_smask(&mask));
+        Ok(image)
+    }
+    
+    fn image_from_handle(&self, image: haru::HPDF_Image) -> Result<Image> {
+        if image.is_null() {
+            return Error::last_err(self.inner.handle);
+        }
+        Ok( Image::from_handle(image, self.inner.clone()) )
+    }
+    
     pub fn save<W: Write>(&self, w: &mut W) -> Result<()> {
         extern "C" fn write_fn<W: Write>(stream: haru::HPDF_Stream, ptr: *const haru::HPDF_BYTE, size: haru::HPDF_UINT) -> haru::HPDF_STATUS {
-            let w : &mut W = unsafe { transmute( (*stream).attr ) };
+            let w : &mut ErrorKeepingWriter<W> = unsafe { transmute( (*stream).attr ) };
             let buf: &[u8] = unsafe { slice::from_raw_parts(ptr, size as usize) };
             
-            match w.write_all(buf) {
-                Ok( () ) => 0,
-                Err( _ ) => 0x1016, // file io error
+            match w.writer.write_all(buf) {
+                Ok( () ) => {
+                    w.written += buf.len() as u64;
+                    haru_trace!("save: wrote {} bytes, {} total", buf.len(), w.written);
+                    0
+                }
+                Err( e ) => {
+                    haru_debug!("save: write failed after {} bytes: {}", w.written, e);
+                    w.error = Some(e);
+                    0x1016 // file io error
+                }
             }
         }
         
+        struct ErrorKeepingWriter<'a, W: Write + 'a> {
+            writer: &'a mut W,
+            error: Option<io::Error>,
+            written: u64,
+        }
+        
+        let mut ew = ErrorKeepingWriter{ writer: w, error: None, written: 0 };
+        
+        haru_debug!("save: starting");
+        
         let mmgr = unsafe { haru::HPDF_GetMMgr(self.inner.handle) };
-        let stream = unsafe { haru::HPDF_CallbackWriter_New(mmgr, Some(write_fn::<W>), w as *mut W as *mut ::libc::c_void) };
+        let stream = unsafe { haru::HPDF_CallbackWriter_New(mmgr, Some(write_fn::<W>), &mut ew as *mut ErrorKeepingWriter<W> as *mut ::libc::c_void) };
         let err = unsafe { haru::HPDF_SaveToExternalStream(self.inner.handle, stream) };
         unsafe { haru::HPDF_Stream_Free(stream); }
         
-        Error::from_status(err)
+        if let Some(e) = ew.error {
+            return Err(Error::from(e));
+        }
+        if err != 0 {
+            for cell in self.inner.font_stream_errors.borrow().iter() {
+                if let Some(e) = cell.take() {
+                    haru_debug!("save: reading an embedded font failed: {}", e);
+                    return Err(Error::from(e));
+                }
+            }
+        }
+        haru_debug!("save: finished with status {:#x} after {} bytes", err, ew.written);
+        Error::from_doc_status(self.inner.handle, err)
+    }
+}
+
+#[cfg(unix)]
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    use std::os::unix::ffi::OsStrExt;
+    Ok(try!(CString::new(path.as_os_str().as_bytes())))
+}
+
+#[cfg(not(unix))]
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    match path.to_str() {
+        Some(s) => Ok(try!(CString::new(s))),
+        None => Error::new_err(::error::Code::InvalidParameter),
     }
 }
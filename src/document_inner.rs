@@ -1,11 +1,51 @@
-use libharu_sys as haru;
-
-pub struct DocumentInner {
-    pub handle: haru::HPDF_Doc,
-}
-
-impl Drop for DocumentInner {
-    fn drop(&mut self) {
-        unsafe { haru::HPDF_Free(self.handle); }
-    }
-}
+use libharu_sys as haru;
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::rc::Rc;
+use std::panic::{self, AssertUnwindSafe};
+
+use error::Error;
+
+pub struct DocumentInner {
+    pub handle: haru::HPDF_Doc,
+    
+    // Boxed so that its address, which libharu holds as the error handler's user data, never changes.
+    pub errors: Box<ErrorState>,
+    
+    /// Where the streams of embedded TrueType fonts keep their read errors. They are read again
+    /// while saving, so a failed save looks here for its cause.
+    pub font_stream_errors: RefCell<Vec<Rc<Cell<Option<io::Error>>>>>,
+}
+
+/// What the error handler installed on each document records and reports to.
+pub struct ErrorState {
+    /// The `(error_no, detail_no)` of the most recent error libharu raised.
+    pub last: Cell<Option<(haru::HPDF_STATUS, haru::HPDF_STATUS)>>,
+    pub hooks: RefCell<Vec<Box<dyn Fn(&Error)>>>,
+}
+
+impl ErrorState {
+    pub fn new() -> ErrorState {
+        ErrorState{ last: Cell::new(None), hooks: RefCell::new(Vec::new()) }
+    }
+}
+
+pub extern "C" fn error_handler(error_no: haru::HPDF_STATUS, detail_no: haru::HPDF_STATUS, user_data: *mut ::libc::c_void) {
+    let state: &ErrorState = unsafe { &*(user_data as *const ErrorState) };
+    state.last.set(Some((error_no, detail_no)));
+    
+    let err = Error::with_detail(error_no, detail_no);
+    haru_warn!("libharu error {:#x} (detail {:#x}): {}", error_no, detail_no, err.code);
+    if let Ok(hooks) = state.hooks.try_borrow() {
+        for hook in hooks.iter() {
+            // A panic must not unwind into libharu.
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| hook(&err)));
+        }
+    }
+}
+
+impl Drop for DocumentInner {
+    fn drop(&mut self) {
+        unsafe { haru::HPDF_Free(self.handle); }
+    }
+}
@@ -0,0 +1,129 @@
+/// The encodings libharu has built in, which map the bytes of a string to glyphs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Encoding {
+    Standard,
+    MacRoman,
+    WinAnsi,
+    
+    /// The font's own built-in encoding. Required for `BaseFont::Symbol` and `BaseFont::ZapfDingbats`.
+    FontSpecific,
+    
+    Iso8859_2,
+    Iso8859_3,
+    Iso8859_4,
+    Iso8859_5,
+    Iso8859_6,
+    Iso8859_7,
+    Iso8859_8,
+    Iso8859_9,
+    Iso8859_10,
+    Iso8859_11,
+    Iso8859_13,
+    Iso8859_14,
+    Iso8859_15,
+    Iso8859_16,
+    Cp1250,
+    Cp1251,
+    Cp1252,
+    Cp1253,
+    Cp1254,
+    Cp1255,
+    Cp1256,
+    Cp1257,
+    Cp1258,
+    Koi8R,
+    
+    /// UTF-8, which every `Document` enables. Only usable with TrueType fonts.
+    Utf8,
+    
+    // The CMap encodings below need `Document::use_cjk_encodings` for their language, and a `CidFont`.
+    // Text in them must be passed to the page as already-encoded bytes.
+    
+    /// Japanese: Shift-JIS with Microsoft extensions, horizontal.
+    Jp90msRksjH,
+    /// Japanese: Shift-JIS with Microsoft extensions, vertical.
+    Jp90msRksjV,
+    /// Japanese: Shift-JIS with Microsoft extensions and proportional Latin characters, horizontal.
+    Jp90mspRksjH,
+    /// Japanese: EUC-JP, horizontal.
+    JpEucH,
+    /// Japanese: EUC-JP, vertical.
+    JpEucV,
+    
+    /// Korean: EUC-KR, horizontal.
+    KscEucH,
+    /// Korean: EUC-KR, vertical.
+    KscEucV,
+    /// Korean: Microsoft Unified Hangul Code, horizontal.
+    KscMsUhcH,
+    /// Korean: Microsoft Unified Hangul Code with half-width Latin characters, horizontal.
+    KscMsUhcHwH,
+    /// Korean: Microsoft Unified Hangul Code with half-width Latin characters, vertical.
+    KscMsUhcHwV,
+    
+    /// Simplified Chinese: EUC-CN (GB 2312), horizontal.
+    GbEucH,
+    /// Simplified Chinese: EUC-CN (GB 2312), vertical.
+    GbEucV,
+    /// Simplified Chinese: GBK, horizontal.
+    GbkEucH,
+    /// Simplified Chinese: GBK, vertical.
+    GbkEucV,
+    
+    /// Traditional Chinese: Big Five with ETen extensions, horizontal.
+    ETenB5H,
+    /// Traditional Chinese: Big Five with ETen extensions, vertical.
+    ETenB5V,
+}
+
+impl Encoding {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Encoding::Standard => "StandardEncoding",
+            Encoding::MacRoman => "MacRomanEncoding",
+            Encoding::WinAnsi => "WinAnsiEncoding",
+            Encoding::FontSpecific => "FontSpecific",
+            Encoding::Iso8859_2 => "ISO8859-2",
+            Encoding::Iso8859_3 => "ISO8859-3",
+            Encoding::Iso8859_4 => "ISO8859-4",
+            Encoding::Iso8859_5 => "ISO8859-5",
+            Encoding::Iso8859_6 => "ISO8859-6",
+            Encoding::Iso8859_7 => "ISO8859-7",
+            Encoding::Iso8859_8 => "ISO8859-8",
+            Encoding::Iso8859_9 => "ISO8859-9",
+            Encoding::Iso8859_10 => "ISO8859-10",
+            Encoding::Iso8859_11 => "ISO8859-11",
+            Encoding::Iso8859_13 => "ISO8859-13",
+            Encoding::Iso8859_14 => "ISO8859-14",
+            Encoding::Iso8859_15 => "ISO8859-15",
+            Encoding::Iso8859_16 => "ISO8859-16",
+            Encoding::Cp1250 => "CP1250",
+            Encoding::Cp1251 => "CP1251",
+            Encoding::Cp1252 => "CP1252",
+            Encoding::Cp1253 => "CP1253",
+            Encoding::Cp1254 => "CP1254",
+            Encoding::Cp1255 => "CP1255",
+            Encoding::Cp1256 => "CP1256",
+            Encoding::Cp1257 => "CP1257",
+            Encoding::Cp1258 => "CP1258",
+            Encoding::Koi8R => "KOI8-R",
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Jp90msRksjH => "90ms-RKSJ-H",
+            Encoding::Jp90msRksjV => "90ms-RKSJ-V",
+            Encoding::Jp90mspRksjH => "90msp-RKSJ-H",
+            Encoding::JpEucH => "EUC-H",
+            Encoding::JpEucV => "EUC-V",
+            Encoding::KscEucH => "KSC-EUC-H",
+            Encoding::KscEucV => "KSC-EUC-V",
+            Encoding::KscMsUhcH => "KSCms-UHC-H",
+            Encoding::KscMsUhcHwH => "KSCms-UHC-HW-H",
+            Encoding::KscMsUhcHwV => "KSCms-UHC-HW-V",
+            Encoding::GbEucH => "GB-EUC-H",
+            Encoding::GbEucV => "GB-EUC-V",
+            Encoding::GbkEucH => "GBK-EUC-H",
+            Encoding::GbkEucV => "GBK-EUC-V",
+            Encoding::ETenB5H => "ETen-B5-H",
+            Encoding::ETenB5V => "ETen-B5-V",
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use libharu_sys as haru;
+use std::ops::BitOr;
+
+/// What readers who open a document with the user password may do. Opening it with the owner
+/// password allows everything.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Permissions {
+    bits: u32,
+}
+
+impl Permissions {
+    pub const PRINT: Permissions = Permissions{ bits: haru::HPDF_ENABLE_PRINT as u32 };
+
+    /// Changing the contents of pages.
+    pub const EDIT: Permissions = Permissions{ bits: haru::HPDF_ENABLE_EDIT_ALL as u32 };
+
+    /// Copying text and graphics out of the document.
+    pub const COPY: Permissions = Permissions{ bits: haru::HPDF_ENABLE_COPY as u32 };
+
+    /// Adding and changing annotations and form fields.
+    pub const ANNOTATE: Permissions = Permissions{ bits: haru::HPDF_ENABLE_EDIT as u32 };
+
+    /// Only reading.
+    pub const NONE: Permissions = Permissions{ bits: haru::HPDF_ENABLE_READ as u32 };
+
+    pub const ALL: Permissions = Permissions{ bits: haru::HPDF_ENABLE_PRINT as u32 | haru::HPDF_ENABLE_EDIT_ALL as u32
+        | haru::HPDF_ENABLE_COPY as u32 | haru::HPDF_ENABLE_EDIT as u32 };
+
+    pub fn contains(&self, other: Permissions) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+}
+
+impl BitOr for Permissions {
+    type Output = Permissions;
+
+    fn bitor(self, other: Permissions) -> Permissions {
+        Permissions{ bits: self.bits | other.bits }
+    }
+}
+
+/// The encryption algorithm.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EncryptionMode {
+    /// RC4 with a 40 bit key, readable by PDF 1.1 viewers.
+    Rc4_40,
+
+    /// RC4 with a key of 5 to 16 bytes, up to 128 bits. Requires PDF 1.4.
+    Rc4_128 { key_len: u32 },
+}
+
+impl EncryptionMode {
+    pub fn as_int(&self) -> haru::HPDF_EncryptMode {
+        match *self {
+            EncryptionMode::Rc4_40 => haru::HPDF_ENCRYPT_R2,
+            EncryptionMode::Rc4_128 { .. } => haru::HPDF_ENCRYPT_R3,
+        }
+    }
+
+    /// The length of the key in bytes.
+    pub fn key_len(&self) -> u32 {
+        match *self {
+            EncryptionMode::Rc4_40 => 5,
+            EncryptionMode::Rc4_128 { key_len } => key_len,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            EncryptionMode::Rc4_40 => true,
+            EncryptionMode::Rc4_128 { key_len } => key_len >= 5 && key_len <= 16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EncryptionMode, Permissions};
+
+    #[test]
+    fn combines_permissions() {
+        let print_and_copy = Permissions::PRINT | Permissions::COPY;
+        assert!(print_and_copy.contains(Permissions::COPY));
+        assert!(!print_and_copy.contains(Permissions::EDIT));
+        assert!(Permissions::ALL.contains(print_and_copy));
+        assert_eq!(Permissions::ALL, Permissions::PRINT | Permissions::EDIT | Permissions::COPY | Permissions::ANNOTATE);
+        assert_eq!(Permissions::NONE.bits(), 0);
+    }
+
+    #[test]
+    fn validates_key_len() {
+        assert!(EncryptionMode::Rc4_40.is_valid());
+        assert!(EncryptionMode::Rc4_128 { key_len: 16 }.is_valid());
+        assert!(!EncryptionMode::Rc4_128 { key_len: 0 }.is_valid());
+        assert!(!EncryptionMode::Rc4_128 { key_len: 17 }.is_valid());
+    }
+}
@@ -1,163 +1,242 @@
 use std::ptr;
+use std::error;
 use std::ffi::NulError;
+use std::fmt;
+use std::io;
 
-#[derive(Debug, Copy, Clone)]
-pub enum Code {
-    Unknown,
-    StringWithInternalNul,
-    WriteError,
-    
-    AllocationFailed,
-    ArrayCountErr,
-    ArrayItemNotFound,
-    ArrayItemUnexpectedType,
-    BinaryLengthErr,
-/*
-#define HPDF_CANNOT_GET_PALLET                    0x1005
-#define HPDF_DICT_COUNT_ERR                       0x1007
-#define HPDF_DICT_ITEM_NOT_FOUND                  0x1008
-#define HPDF_DICT_ITEM_UNEXPECTED_TYPE            0x1009
-#define HPDF_DICT_STREAM_LENGTH_NOT_FOUND         0x100A
-#define HPDF_DOC_ENCRYPTDICT_NOT_FOUND            0x100B
-#define HPDF_DOC_INVALID_OBJECT                   0x100C
-#define HPDF_DUPLICATE_REGISTRATION               0x100E
-#define HPDF_EXCEED_JWW_CODE_NUM_LIMIT            0x100F
-#define HPDF_ENCRYPT_INVALID_PASSWORD             0x1011
-#define HPDF_ERR_UNKNOWN_CLASS                    0x1013
-#define HPDF_EXCEED_GSTATE_LIMIT                  0x1014*/
-
-    FailedToAllocateMemory,
-    FileIoError,
-/*
-#define HPDF_FILE_OPEN_ERROR                      0x1017
-#define HPDF_FONT_EXISTS                          0x1019
-#define HPDF_FONT_INVALID_WIDTHS_TABLE            0x101A
-#define HPDF_INVALID_AFM_HEADER                   0x101B
-#define HPDF_INVALID_ANNOTATION                   0x101C
-#define HPDF_INVALID_BIT_PER_COMPONENT            0x101E
-#define HPDF_INVALID_CHAR_MATRICS_DATA            0x101F
-#define HPDF_INVALID_COLOR_SPACE                  0x1020
-#define HPDF_INVALID_COMPRESSION_MODE             0x1021
-#define HPDF_INVALID_DATE_TIME                    0x1022
-#define HPDF_INVALID_DESTINATION                  0x1023
-#define HPDF_INVALID_DOCUMENT                     0x1025
-#define HPDF_INVALID_DOCUMENT_STATE               0x1026
-#define HPDF_INVALID_ENCODER                      0x1027
-#define HPDF_INVALID_ENCODER_TYPE                 0x1028
-#define HPDF_INVALID_ENCODING_NAME                0x102B
-#define HPDF_INVALID_ENCRYPT_KEY_LEN              0x102C
-#define HPDF_INVALID_FONTDEF_DATA                 0x102D
-#define HPDF_INVALID_FONTDEF_TYPE                 0x102E
-#define HPDF_INVALID_FONT_NAME                    0x102F
-#define HPDF_INVALID_IMAGE                        0x1030
-#define HPDF_INVALID_JPEG_DATA                    0x1031
-#define HPDF_INVALID_N_DATA                       0x1032
-#define HPDF_INVALID_OBJECT                       0x1033
-#define HPDF_INVALID_OBJ_ID                       0x1034
-#define HPDF_INVALID_OPERATION                    0x1035
-#define HPDF_INVALID_OUTLINE                      0x1036
-#define HPDF_INVALID_PAGE                         0x1037
-#define HPDF_INVALID_PAGES                        0x1038
-#define HPDF_INVALID_PARAMETER                    0x1039
-#define HPDF_INVALID_PNG_IMAGE                    0x103B
-#define HPDF_INVALID_STREAM                       0x103C
-#define HPDF_MISSING_FILE_NAME_ENTRY              0x103D
-#define HPDF_INVALID_TTC_FILE                     0x103F
-#define HPDF_INVALID_TTC_INDEX                    0x1040
-#define HPDF_INVALID_WX_DATA                      0x1041
-#define HPDF_ITEM_NOT_FOUND                       0x1042
-#define HPDF_LIBPNG_ERROR                         0x1043
-#define HPDF_NAME_INVALID_VALUE                   0x1044
-#define HPDF_NAME_OUT_OF_RANGE                    0x1045
-#define HPDF_PAGE_INVALID_PARAM_COUNT             0x1048
-#define HPDF_PAGES_MISSING_KIDS_ENTRY             0x1049
-#define HPDF_PAGE_CANNOT_FIND_OBJECT              0x104A
-#define HPDF_PAGE_CANNOT_GET_ROOT_PAGES           0x104B
-#define HPDF_PAGE_CANNOT_RESTORE_GSTATE           0x104C
-#define HPDF_PAGE_CANNOT_SET_PARENT               0x104D
-#define HPDF_PAGE_FONT_NOT_FOUND                  0x104E
-#define HPDF_PAGE_INVALID_FONT                    0x104F
-#define HPDF_PAGE_INVALID_FONT_SIZE               0x1050
-#define HPDF_PAGE_INVALID_GMODE                   0x1051
-#define HPDF_PAGE_INVALID_INDEX                   0x1052
-#define HPDF_PAGE_INVALID_ROTATE_VALUE            0x1053
-#define HPDF_PAGE_INVALID_SIZE                    0x1054
-#define HPDF_PAGE_INVALID_XOBJECT                 0x1055
-#define HPDF_PAGE_OUT_OF_RANGE                    0x1056
-#define HPDF_REAL_OUT_OF_RANGE                    0x1057
-#define HPDF_STREAM_EOF                           0x1058
-#define HPDF_STREAM_READLN_CONTINUE               0x1059
-#define HPDF_STRING_OUT_OF_RANGE                  0x105B
-#define HPDF_THIS_FUNC_WAS_SKIPPED                0x105C
-#define HPDF_TTF_CANNOT_EMBEDDING_FONT            0x105D
-#define HPDF_TTF_INVALID_CMAP                     0x105E
-#define HPDF_TTF_INVALID_FOMAT                    0x105F
-#define HPDF_TTF_MISSING_TABLE                    0x1060
-#define HPDF_UNSUPPORTED_FONT_TYPE                0x1061
-#define HPDF_UNSUPPORTED_FUNC                     0x1062
-#define HPDF_UNSUPPORTED_JPEG_FORMAT              0x1063
-#define HPDF_UNSUPPORTED_TYPE1_FONT               0x1064
-#define HPDF_XREF_COUNT_ERR                       0x1065
-#define HPDF_ZLIB_ERROR                           0x1066
-#define HPDF_INVALID_PAGE_INDEX                   0x1067
-#define HPDF_INVALID_URI                          0x1068
-#define HPDF_PAGE_LAYOUT_OUT_OF_RANGE             0x1069
-#define HPDF_PAGE_MODE_OUT_OF_RANGE               0x1070
-#define HPDF_PAGE_NUM_STYLE_OUT_OF_RANGE          0x1071
-#define HPDF_ANNOT_INVALID_ICON                   0x1072
-#define HPDF_ANNOT_INVALID_BORDER_STYLE           0x1073
-#define HPDF_PAGE_INVALID_DIRECTION               0x1074
-#define HPDF_INVALID_FONT                         0x1075
-#define HPDF_PAGE_INSUFFICIENT_SPACE              0x1076
-#define HPDF_PAGE_INVALID_DISPLAY_TIME            0x1077
-#define HPDF_PAGE_INVALID_TRANSITION_TIME         0x1078
-#define HPDF_INVALID_PAGE_SLIDESHOW_TYPE          0x1079
-#define HPDF_EXT_GSTATE_OUT_OF_RANGE              0x1080
-#define HPDF_INVALID_EXT_GSTATE                   0x1081
-#define HPDF_EXT_GSTATE_READ_ONLY                 0x1082
-#define HPDF_INVALID_U3D_DATA                     0x1083
-#define HPDF_NAME_CANNOT_GET_NAMES                0x1084
-#define HPDF_INVALID_ICC_COMPONENT_NUM            0x1085
-*/
+use libharu_sys as haru;
+
+/// Generates `Code` along with the mapping between its variants and libharu's `HPDF_STATUS` values.
+macro_rules! codes {
+    (
+        own { $($own:tt)* }
+        libharu { $($status:literal => $variant:ident: $description:literal,)* }
+    ) => {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        pub enum Code {
+            $($own)*
+            $($variant,)*
+        }
+
+        impl Code {
+            pub fn from_status(status: haru::HPDF_STATUS) -> Option<Code> {
+                Some(match status {
+                    $($status => Code::$variant,)*
+                    _ => { return None; }
+                })
+            }
+
+            /// The libharu status this code corresponds to, or `None` for codes that originate in this crate.
+            pub fn as_status(&self) -> Option<haru::HPDF_STATUS> {
+                Some(match *self {
+                    $(Code::$variant => $status,)*
+                    _ => { return None; }
+                })
+            }
+
+            /// A short, human-readable description of the error.
+            pub fn description(&self) -> &'static str {
+                match *self {
+                    $(Code::$variant => $description,)*
+                    _ => self.own_description(),
+                }
+            }
+        }
+    }
 }
 
-use libharu_sys as haru;
+codes! {
+    own {
+        Unknown,
+        StringWithInternalNul,
+        WriteError,
+        AllocationFailed,
 
-#[derive(Debug, Copy, Clone)]
-pub struct Error {
-    pub code: Code,
-    pub detail: Option<Code>,
+        /// An I/O error from a reader or writer supplied to this crate. `Error::io_error` holds the original error.
+        Io(io::ErrorKind),
+    }
+    libharu {
+        0x1001 => ArrayCountErr: "internal array has too many elements",
+        0x1002 => ArrayItemNotFound: "internal array item not found",
+        0x1003 => ArrayItemUnexpectedType: "internal array item has an unexpected type",
+        0x1004 => BinaryLengthErr: "binary data is too long",
+        0x1005 => CannotGetPallet: "cannot get the palette of a PNG image",
+        0x1007 => DictCountErr: "dictionary has too many elements",
+        0x1008 => DictItemNotFound: "dictionary item not found",
+        0x1009 => DictItemUnexpectedType: "dictionary item has an unexpected type",
+        0x100A => DictStreamLengthNotFound: "stream dictionary is missing its length",
+        0x100B => DocEncryptDictNotFound: "encryption requested but no password has been set",
+        0x100C => DocInvalidObject: "object does not belong to this document",
+        0x100E => DuplicateRegistration: "object was registered twice",
+        0x100F => ExceedJwwCodeNumLimit: "too many JWW codes",
+        0x1011 => EncryptInvalidPassword: "owner password is empty or the same as the user password",
+        0x1013 => UnknownClass: "object has an unknown class",
+        0x1014 => ExceedGStateLimit: "graphics state stack is too deep",
+        0x1015 => FailedToAllocateMemory: "memory allocation failed",
+        0x1016 => FileIoError: "file I/O failed",
+        0x1017 => FileOpenError: "file could not be opened",
+        0x1019 => FontExists: "a font with the same name is already loaded",
+        0x101A => FontInvalidWidthsTable: "font widths table is invalid",
+        0x101B => InvalidAfmHeader: "AFM file has an invalid header",
+        0x101C => InvalidAnnotation: "invalid annotation",
+        0x101E => InvalidBitPerComponent: "invalid bits per component",
+        0x101F => InvalidCharMetricsData: "AFM file has invalid character metrics",
+        0x1020 => InvalidColorSpace: "invalid color space",
+        0x1021 => InvalidCompressionMode: "invalid compression mode",
+        0x1022 => InvalidDateTime: "invalid date or time",
+        0x1023 => InvalidDestination: "invalid destination",
+        0x1025 => InvalidDocument: "invalid document",
+        0x1026 => InvalidDocumentState: "operation is not allowed in the document's current state",
+        0x1027 => InvalidEncoder: "invalid encoder",
+        0x1028 => InvalidEncoderType: "encoder type is not usable here",
+        0x102B => InvalidEncodingName: "unknown encoding name",
+        0x102C => InvalidEncryptKeyLen: "invalid encryption key length",
+        0x102D => InvalidFontDefData: "font definition data is invalid",
+        0x102E => InvalidFontDefType: "font definition has an unsupported type",
+        0x102F => InvalidFontName: "unknown font name",
+        0x1030 => InvalidImage: "invalid image",
+        0x1031 => InvalidJpegData: "invalid JPEG data",
+        0x1032 => InvalidNData: "invalid N data",
+        0x1033 => InvalidObject: "invalid object",
+        0x1034 => InvalidObjId: "invalid object id",
+        0x1035 => InvalidOperation: "invalid operation",
+        0x1036 => InvalidOutline: "invalid outline",
+        0x1037 => InvalidPage: "invalid page",
+        0x1038 => InvalidPages: "invalid pages object",
+        0x1039 => InvalidParameter: "invalid parameter",
+        0x103B => InvalidPngImage: "invalid PNG image",
+        0x103C => InvalidStream: "invalid stream",
+        0x103D => MissingFileNameEntry: "file name entry is missing",
+        0x103F => InvalidTtcFile: "invalid TrueType collection file",
+        0x1040 => InvalidTtcIndex: "face index is out of range for the TrueType collection",
+        0x1041 => InvalidWxData: "AFM file has invalid glyph width data",
+        0x1042 => ItemNotFound: "item not found",
+        0x1043 => LibpngError: "libpng reported an error",
+        0x1044 => NameInvalidValue: "invalid name value",
+        0x1045 => NameOutOfRange: "name is too long",
+        0x1048 => PageInvalidParamCount: "wrong number of parameters for page operation",
+        0x1049 => PagesMissingKidsEntry: "pages object is missing its kids entry",
+        0x104A => PageCannotFindObject: "page object not found",
+        0x104B => PageCannotGetRootPages: "cannot get the root pages object",
+        0x104C => PageCannotRestoreGState: "there is no saved graphics state to restore",
+        0x104D => PageCannotSetParent: "cannot set the parent of a page",
+        0x104E => PageFontNotFound: "no font is set on the page",
+        0x104F => PageInvalidFont: "invalid font",
+        0x1050 => PageInvalidFontSize: "invalid font size",
+        0x1051 => PageInvalidGMode: "operation is not allowed in the page's current graphics mode",
+        0x1052 => PageInvalidIndex: "invalid page index",
+        0x1053 => PageInvalidRotateValue: "page rotation must be a multiple of 90 degrees",
+        0x1054 => PageInvalidSize: "invalid page size",
+        0x1055 => PageInvalidXObject: "invalid XObject",
+        0x1056 => PageOutOfRange: "page value is out of range",
+        0x1057 => RealOutOfRange: "real number is out of range",
+        0x1058 => StreamEof: "unexpected end of stream",
+        0x1059 => StreamReadlnContinue: "stream line was too long to read at once",
+        0x105B => StringOutOfRange: "string is too long",
+        0x105C => ThisFuncWasSkipped: "function was skipped because of an earlier error",
+        0x105D => TtfCannotEmbedFont: "TrueType font does not permit embedding",
+        0x105E => TtfInvalidCmap: "TrueType font has an invalid cmap table",
+        0x105F => TtfInvalidFormat: "TrueType font has an invalid format",
+        0x1060 => TtfMissingTable: "TrueType font is missing required table",
+        0x1061 => UnsupportedFontType: "unsupported font type",
+        0x1062 => UnsupportedFunc: "libharu was built without support for this function",
+        0x1063 => UnsupportedJpegFormat: "unsupported JPEG format",
+        0x1064 => UnsupportedType1Font: "unsupported Type 1 font",
+        0x1065 => XrefCountErr: "too many cross-reference entries",
+        0x1066 => ZlibError: "zlib reported an error",
+        0x1067 => InvalidPageIndex: "invalid page index",
+        0x1068 => InvalidUri: "invalid URI",
+        0x1069 => PageLayoutOutOfRange: "page layout is out of range",
+        0x1070 => PageModeOutOfRange: "page mode is out of range",
+        0x1071 => PageNumStyleOutOfRange: "page numbering style is out of range",
+        0x1072 => AnnotInvalidIcon: "invalid annotation icon",
+        0x1073 => AnnotInvalidBorderStyle: "invalid annotation border style",
+        0x1074 => PageInvalidDirection: "invalid page direction",
+        0x1075 => InvalidFont: "invalid font",
+        0x1076 => PageInsufficientSpace: "text does not fit in the given area",
+        0x1077 => PageInvalidDisplayTime: "invalid page display time",
+        0x1078 => PageInvalidTransitionTime: "invalid page transition time",
+        0x1079 => InvalidPageSlideshowType: "invalid slideshow type",
+        0x1080 => ExtGStateOutOfRange: "extended graphics state value is out of range",
+        0x1081 => InvalidExtGState: "invalid extended graphics state",
+        0x1082 => ExtGStateReadOnly: "extended graphics state is read-only once used",
+        0x1083 => InvalidU3dData: "invalid U3D data",
+        0x1084 => NameCannotGetNames: "cannot get the names dictionary",
+        0x1085 => InvalidIccComponentNum: "invalid number of ICC color components",
+    }
 }
 
 impl Code {
-    pub fn from_status(status: haru::HPDF_STATUS) -> Option<Code> {
-        Some(match status {
-            0x1001 => Code::ArrayCountErr,
-            0x1002 => Code::ArrayItemNotFound,
-            0x1003 => Code::ArrayItemUnexpectedType,
-            0x1004 => Code::BinaryLengthErr,
-            0x1015 => Code::FailedToAllocateMemory,
-            0x1016 => Code::FileIoError,
-            _ => { return None; }
-        })
-    }
-    
+    fn own_description(&self) -> &'static str {
+        match *self {
+            Code::StringWithInternalNul => "string contains an internal nul byte",
+            Code::WriteError => "write failed",
+            Code::AllocationFailed => "memory allocation failed",
+            Code::Io(_) => "I/O error",
+            _ => "unknown error",
+        }
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+#[derive(Debug)]
+pub struct Error {
+    pub code: Code,
+
+    /// The detail number libharu reported alongside the error, if any. Its meaning depends on `code`;
+    /// for example, it is the C library's `errno` for I/O errors.
+    pub detail: Option<haru::HPDF_STATUS>,
+
+    cause: Option<io::Error>,
 }
 
 pub type Result<T> = ::std::result::Result<T, Error>;
 
 impl Error {
     pub fn new_err<T>(code: Code) -> Result<T> {
-        Err(Error{code: code, detail: None})
+        Err(Error{code: code, detail: None, cause: None})
     }
-    
+
     pub fn from_status(status: haru::HPDF_STATUS) -> Result<()> {
         if status == 0 {
             return Ok( () );
         }
         Error::new_err(Code::from_status(status).unwrap_or(Code::Unknown))
     }
-    
+
+    /// Like `from_status`, but also picks up the detail number libharu recorded on the document.
+    pub fn from_doc_status(doc: haru::HPDF_Doc, status: haru::HPDF_STATUS) -> Result<()> {
+        if status == 0 {
+            return Ok( () );
+        }
+        // Some functions return a status without recording it, leaving an older error's detail behind
+        let detail = unsafe {
+            if haru::HPDF_GetError(doc) == status { haru::HPDF_GetErrorDetail(doc) } else { 0 }
+        };
+        Err(Error::with_detail(status, detail))
+    }
+
+    /// The error most recently recorded on the document, for use when a libharu function signals failure
+    /// by returning null rather than a status.
+    pub fn last_err<T>(doc: haru::HPDF_Doc) -> Result<T> {
+        let (status, detail) = unsafe { (haru::HPDF_GetError(doc), haru::HPDF_GetErrorDetail(doc)) };
+        Err(Error::with_detail(status, detail))
+    }
+
+    /// Builds an error from a libharu status and detail number, as passed to an error handler.
+    pub fn with_detail(status: haru::HPDF_STATUS, detail: haru::HPDF_STATUS) -> Error {
+        Error{
+            code: Code::from_status(status).unwrap_or(Code::Unknown),
+            detail: if detail == 0 { None } else { Some(detail) },
+            cause: None,
+        }
+    }
+
     pub fn check_non_null<T>(p: *mut T) -> Result<*mut T> {
         if p == ptr::null_mut() {
             Error::new_err(Code::FailedToAllocateMemory)
@@ -165,10 +244,38 @@ impl Error {
             Ok( p )
         }
     }
+
+    /// The I/O error that caused this error, if there was one.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.cause.as_ref()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "{}", self.code));
+        if let Some(detail) = self.detail {
+            // Details are often errno values, which are read in decimal
+            try!(write!(f, " (detail {})", detail));
+        }
+        Ok( () )
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.cause.as_ref().map(|cause| cause as &(dyn error::Error + 'static))
+    }
 }
 
 impl From<NulError> for Error {
     fn from(_: NulError) -> Error {
-        Error{code: Code::StringWithInternalNul, detail: None}
+        Error{code: Code::StringWithInternalNul, detail: None, cause: None}
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error{code: Code::Io(err.kind()), detail: None, cause: Some(err)}
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,14 @@
-
 use libharu_sys as haru;
 use document_inner::DocumentInner;
+use page::Rect;
+use std::borrow::Cow;
+use std::ffi::CStr;
 use std::rc::Rc;
 use std::f32;
 
+/// A font, with an encoding, belonging to a document.
+///
+/// Metrics are given as a fraction of the font size, so multiply by the size to get points.
 #[derive(Clone)]
 pub struct Font {
     handle: haru::HPDF_Font,
@@ -22,31 +27,117 @@ impl  Font {
         }
     }
     
+    /// How far glyphs extend below the baseline. This is negative.
     pub fn get_descent(&self) -> f32 {
         unsafe {
-            haru::HPDF_Font_GetAscent(self.handle) as f32 / 1000.0
+            haru::HPDF_Font_GetDescent(self.handle) as f32 / 1000.0
+        }
+    }
+    
+    /// The height of flat capital letters, such as "H", above the baseline.
+    pub fn cap_height(&self) -> f32 {
+        unsafe {
+            haru::HPDF_Font_GetCapHeight(self.handle) as f32 / 1000.0
+        }
+    }
+    
+    /// The height of flat lowercase letters, such as "x", above the baseline.
+    pub fn x_height(&self) -> f32 {
+        unsafe {
+            haru::HPDF_Font_GetXHeight(self.handle) as f32 / 1000.0
+        }
+    }
+    
+    /// The box that would enclose every glyph in the font if they were drawn on top of each other at the origin.
+    pub fn bbox(&self) -> Rect {
+        let bbox = unsafe { haru::HPDF_Font_GetBBox(self.handle) };
+        Rect::from_edges(bbox.left / 1000.0, bbox.bottom / 1000.0, bbox.right / 1000.0, bbox.top / 1000.0)
+    }
+    
+    /// The advance width of a character. This is 0 for characters the font has no glyph for,
+    /// and for characters outside the Basic Multilingual Plane, which libharu cannot look up.
+    pub fn unicode_width(&self, c: char) -> f32 {
+        let code = c as u32;
+        if code > 0xFFFF {
+            return 0.0;
+        }
+        unsafe {
+            haru::HPDF_Font_GetUnicodeWidth(self.handle, code as haru::HPDF_UNICODE) as f32 / 1000.0
         }
     }
     
+    /// The distance from the lowest descender to the highest ascender at the given size, in points.
+    pub fn line_height(&self, size: f32) -> f32 {
+        (self.get_ascent() - self.get_descent()) * size
+    }
+    
+    /// The font's PostScript name, such as "Helvetica-Bold".
+    pub fn font_name(&self) -> Cow<str> {
+        unsafe { c_str_or_empty(haru::HPDF_Font_GetFontName(self.handle)) }
+    }
+    
+    /// The name of the encoding the font was loaded with, such as "WinAnsiEncoding" or "UTF-8".
+    pub fn encoding_name(&self) -> Cow<str> {
+        unsafe { c_str_or_empty(haru::HPDF_Font_GetEncodingName(self.handle)) }
+    }
+    
     pub unsafe fn get_handle(&self) -> haru::HPDF_Page {
         self.handle
     }
     
     pub fn measure_width(&self, text: &str, size: f32) -> f32 {
+        self.measure(text, size, f32::MAX, 0.0, 0.0, false).width
+    }
+    
+    /// Measures how much of `text` fits in `max_width` points. With `wrap`, the text is only cut where a
+    /// line could break, after a space; without it, it is cut after the last character that fits.
+    pub fn measure(&self, text: &str, size: f32, max_width: f32, char_space: f32, word_space: f32, wrap: bool) -> Measurement {
         let mut actual_width: f32 = 0.0;
-        unsafe {
+        let bytes_fit = unsafe {
             haru::HPDF_Font_MeasureText(
                 self.handle,
                 text.as_bytes().as_ptr(),
                 text.as_bytes().len() as u32,
-                f32::MAX, // width to fit in
+                max_width,
                 size,
-                0.0, // char space
-                0.0, // word space
-                0, // word wrap off
+                char_space,
+                word_space,
+                wrap as haru::HPDF_BOOL,
                 &mut actual_width
-            );
+            )
+        } as usize;
+        
+        // libharu counts in bytes; never report a split in the middle of a character.
+        let mut bytes_fit = bytes_fit.min(text.len());
+        while !text.is_char_boundary(bytes_fit) {
+            bytes_fit -= 1;
         }
-        actual_width
+        
+        Measurement{
+            bytes_fit: bytes_fit,
+            chars_fit: text[..bytes_fit].chars().count(),
+            width: actual_width,
+        }
+    }
+}
+
+/// The result of `Font::measure`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Measurement {
+    /// How many bytes of the text fit. This is always on a character boundary.
+    pub bytes_fit: usize,
+    
+    /// How many characters of the text fit.
+    pub chars_fit: usize,
+    
+    /// The width, in points, of the text that fits.
+    pub width: f32,
+}
+
+unsafe fn c_str_or_empty<'a>(p: *const ::libc::c_char) -> Cow<'a, str> {
+    if p.is_null() {
+        Cow::Borrowed("")
+    } else {
+        CStr::from_ptr(p).to_string_lossy()
     }
 }
@@ -0,0 +1,47 @@
+use libharu_sys as haru;
+use std::ops::{Deref, DerefMut};
+
+use error::{Error, Result};
+use page::Page;
+
+/// A saved graphics state, created by `Page::save_state`. Changes made to the page through it, such as
+/// transformations, clipping and colors, are undone when it is dropped.
+pub struct GraphicsState<'a> {
+    page: &'a mut Page,
+    restored: bool,
+}
+
+impl<'a> GraphicsState<'a> {
+    /// Wraps a page on which `HPDF_Page_GSave` has just succeeded.
+    pub(crate) fn new(page: &'a mut Page) -> GraphicsState<'a> {
+        GraphicsState{ page: page, restored: false }
+    }
+    
+    /// Restores the graphics state, reporting any error that dropping it would ignore.
+    pub fn restore(mut self) -> Result<()> {
+        self.restored = true;
+        Error::from_doc_status(unsafe { self.page.get_doc_handle() }, unsafe { haru::HPDF_Page_GRestore(self.page.get_handle()) } )
+    }
+}
+
+impl<'a> Deref for GraphicsState<'a> {
+    type Target = Page;
+    
+    fn deref(&self) -> &Page {
+        &*self.page
+    }
+}
+
+impl<'a> DerefMut for GraphicsState<'a> {
+    fn deref_mut(&mut self) -> &mut Page {
+        &mut *self.page
+    }
+}
+
+impl<'a> Drop for GraphicsState<'a> {
+    fn drop(&mut self) {
+        if !self.restored {
+            unsafe { haru::HPDF_Page_GRestore(self.page.get_handle()); }
+        }
+    }
+}
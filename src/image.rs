@@ -0,0 +1,152 @@
+use libharu_sys as haru;
+use document_inner::DocumentInner;
+use std::ffi::CStr;
+use std::rc::Rc;
+
+use error::{Code, Error, Result};
+
+/// The color space of an image's pixels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorSpace {
+    DeviceGray = haru::HPDF_CS_DEVICE_GRAY as isize,
+    DeviceRgb = haru::HPDF_CS_DEVICE_RGB as isize,
+    DeviceCmyk = haru::HPDF_CS_DEVICE_CMYK as isize,
+
+    /// Pixels are indexes into a palette, as in PNG files with few colors.
+    Indexed = haru::HPDF_CS_INDEXED as isize,
+}
+
+impl ColorSpace {
+    /// The color space with the given PDF name, such as "DeviceRGB".
+    pub fn from_name(name: &str) -> Option<ColorSpace> {
+        Some(match name {
+            "DeviceGray" => ColorSpace::DeviceGray,
+            "DeviceRGB" => ColorSpace::DeviceRgb,
+            "DeviceCMYK" => ColorSpace::DeviceCmyk,
+            "Indexed" => ColorSpace::Indexed,
+            _ => { return None; }
+        })
+    }
+
+    /// How many components each pixel has, or `None` for `Indexed`.
+    pub fn components(&self) -> Option<usize> {
+        match *self {
+            ColorSpace::DeviceGray => Some(1),
+            ColorSpace::DeviceRgb => Some(3),
+            ColorSpace::DeviceCmyk => Some(4),
+            ColorSpace::Indexed => None,
+        }
+    }
+}
+
+/// A raster image belonging to a document. It can be drawn any number of times, on any of its pages.
+#[derive(Clone)]
+pub struct Image {
+    handle: haru::HPDF_Image,
+    doc: Rc<DocumentInner>
+}
+
+impl Image {
+    pub fn from_handle(handle: haru::HPDF_Image, doc: Rc<DocumentInner>) -> Image {
+        Image{ handle: handle, doc: doc }
+    }
+
+    pub unsafe fn get_handle(&self) -> haru::HPDF_Image {
+        self.handle
+    }
+
+    /// The width in pixels.
+    pub fn width(&self) -> u32 {
+        unsafe { haru::HPDF_Image_GetWidth(self.handle) }
+    }
+
+    /// The height in pixels.
+    pub fn height(&self) -> u32 {
+        unsafe { haru::HPDF_Image_GetHeight(self.handle) }
+    }
+
+    pub fn bits_per_component(&self) -> u32 {
+        unsafe { haru::HPDF_Image_GetBitsPerComponent(self.handle) }
+    }
+
+    /// Makes pixels whose components all fall in the given inclusive ranges transparent.
+    /// Only for RGB images; the ranges are `(min, max)` for red, green and blue.
+    pub fn set_color_mask(&mut self, red: (u32, u32), green: (u32, u32), blue: (u32, u32)) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Image_SetColorMask(self.handle,
+            red.0, red.1, green.0, green.1, blue.0, blue.1) } )
+    }
+
+    /// Uses `mask`, a grayscale image of the same size, as this image's alpha channel.
+    pub fn add_smask(&mut self, mask: &Image) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Image_AddSMask(self.handle, mask.handle) } )
+    }
+
+    pub fn color_space(&self) -> Option<ColorSpace> {
+        let name = unsafe { haru::HPDF_Image_GetColorSpace(self.handle) };
+        if name.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(name) }.to_str().ok().and_then(ColorSpace::from_name)
+    }
+}
+
+/// The number of bytes libharu reads for a raw image. Rows are packed, so this fails with
+/// `Code::InvalidImage` unless each row ends on a byte boundary.
+pub fn raw_image_len(width: u32, height: u32, color_space: ColorSpace, bits_per_component: u32) -> Result<usize> {
+    let components = match color_space.components() {
+        Some(components) => components,
+        None => { return Error::new_err(Code::InvalidColorSpace); }
+    };
+    match bits_per_component {
+        1 | 2 | 4 | 8 => (),
+        _ => { return Error::new_err(Code::InvalidBitPerComponent); }
+    }
+    let row_bits = (width as usize).checked_mul(components).and_then(|n| n.checked_mul(bits_per_component as usize));
+    match row_bits {
+        Some(bits) if bits % 8 == 0 => match (bits / 8).checked_mul(height as usize) {
+            Some(len) => Ok(len),
+            None => Error::new_err(Code::InvalidImage),
+        },
+        Some(_) | None => Error::new_err(Code::InvalidImage),
+    }
+}
+
+/// Splits 8-bit RGBA pixels into RGB pixels and a grayscale alpha channel.
+pub fn split_alpha(rgba: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let pixels = rgba.len() / 4;
+    let mut rgb = Vec::with_capacity(pixels * 3);
+    let mut alpha = Vec::with_capacity(pixels);
+    for pixel in rgba.chunks(4).take(pixels) {
+        rgb.extend_from_slice(&pixel[..3]);
+        alpha.push(pixel[3]);
+    }
+    (rgb, alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorSpace, raw_image_len, split_alpha};
+    use error::Code;
+
+    #[test]
+    fn measures_packed_rows() {
+        assert_eq!(raw_image_len(16, 2, ColorSpace::DeviceGray, 1).ok(), Some(4));
+        assert_eq!(raw_image_len(2, 3, ColorSpace::DeviceRgb, 4).ok(), Some(9));
+        assert_eq!(raw_image_len(3, 2, ColorSpace::DeviceRgb, 8).ok(), Some(18));
+        assert_eq!(raw_image_len(3, 2, ColorSpace::Indexed, 8).err().map(|e| e.code), Some(Code::InvalidColorSpace));
+        assert_eq!(raw_image_len(3, 2, ColorSpace::DeviceGray, 16).err().map(|e| e.code), Some(Code::InvalidBitPerComponent));
+    }
+
+    #[test]
+    fn rejects_rows_that_end_inside_a_byte() {
+        assert_eq!(raw_image_len(3, 2, ColorSpace::DeviceGray, 1).err().map(|e| e.code), Some(Code::InvalidImage));
+        assert_eq!(raw_image_len(1, 2, ColorSpace::DeviceCmyk, 2).ok(), Some(2));
+    }
+
+    #[test]
+    fn splits_alpha() {
+        let (rgb, alpha) = split_alpha(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(rgb, vec![1, 2, 3, 5, 6, 7]);
+        assert_eq!(alpha, vec![4, 8]);
+    }
+}
@@ -0,0 +1,137 @@
+use libharu_sys as haru;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Datelike, Offset, TimeZone, Timelike};
+
+/// A text attribute of the document information dictionary.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InfoAttribute {
+    Title,
+    Author,
+    Subject,
+    Keywords,
+
+    /// The application that created the original document.
+    Creator,
+
+    /// The application that converted it to PDF.
+    Producer,
+}
+
+impl InfoAttribute {
+    pub fn as_int(&self) -> haru::HPDF_InfoType {
+        match *self {
+            InfoAttribute::Title => haru::HPDF_INFO_TITLE,
+            InfoAttribute::Author => haru::HPDF_INFO_AUTHOR,
+            InfoAttribute::Subject => haru::HPDF_INFO_SUBJECT,
+            InfoAttribute::Keywords => haru::HPDF_INFO_KEYWORDS,
+            InfoAttribute::Creator => haru::HPDF_INFO_CREATOR,
+            InfoAttribute::Producer => haru::HPDF_INFO_PRODUCER,
+        }
+    }
+}
+
+/// A date and time, with the offset of its time zone from UTC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+
+    /// Minutes ahead of UTC. Negative west of Greenwich.
+    pub utc_offset_minutes: i32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32, utc_offset_minutes: i32) -> Date {
+        Date{
+            year: year,
+            month: month,
+            day: day,
+            hour: hour,
+            minute: minute,
+            second: second,
+            utc_offset_minutes: utc_offset_minutes,
+        }
+    }
+
+    /// Parses a PDF date string, such as "D:20240131093000+01'00'". Fields after the year may be left out.
+    pub fn parse(text: &str) -> Option<Date> {
+        let text = if text.starts_with("D:") { &text[2..] } else { text };
+        let digits_end = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+        let (digits, zone) = text.split_at(digits_end);
+        if digits.len() < 4 || digits.len() > 14 || digits.len() % 2 != 0 {
+            return None;
+        }
+        
+        let field = |start: usize, default: u32| -> u32 {
+            digits.get(start..start + 2).and_then(|field| field.parse().ok()).unwrap_or(default)
+        };
+        let year = match digits[..4].parse() {
+            Ok(year) => year,
+            Err(_) => { return None; }
+        };
+        
+        let utc_offset_minutes = match zone.chars().next() {
+            None | Some('Z') => 0,
+            Some(sign) if sign == '+' || sign == '-' => {
+                let zone_digits: String = zone[1..].chars().filter(|c| c.is_ascii_digit()).collect();
+                let hours: i32 = match zone_digits.get(..2).and_then(|hours| hours.parse().ok()) {
+                    Some(hours) => hours,
+                    None => { return None; }
+                };
+                let minutes: i32 = zone_digits.get(2..4).and_then(|minutes| minutes.parse().ok()).unwrap_or(0);
+                let offset = hours * 60 + minutes;
+                if sign == '-' { -offset } else { offset }
+            }
+            _ => { return None; }
+        };
+        
+        Some(Date::new(year, field(4, 1), field(6, 1), field(8, 0), field(10, 0), field(12, 0), utc_offset_minutes))
+    }
+
+    pub fn to_haru(&self) -> haru::HPDF_Date {
+        let ind = match self.utc_offset_minutes {
+            0 => b'Z',
+            offset if offset > 0 => b'+',
+            _ => b'-',
+        };
+        let offset = self.utc_offset_minutes.abs();
+        haru::HPDF_Date{
+            year: self.year as haru::HPDF_INT,
+            month: self.month as haru::HPDF_INT,
+            day: self.day as haru::HPDF_INT,
+            hour: self.hour as haru::HPDF_INT,
+            minutes: self.minute as haru::HPDF_INT,
+            seconds: self.second as haru::HPDF_INT,
+            ind: ind as ::libc::c_char,
+            off_hour: (offset / 60) as haru::HPDF_INT,
+            off_minutes: (offset % 60) as haru::HPDF_INT,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> From<DateTime<Tz>> for Date {
+    fn from(date: DateTime<Tz>) -> Date {
+        Date::new(date.year(), date.month(), date.day(), date.hour(), date.minute(), date.second(),
+            date.offset().fix().local_minus_utc() / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Date;
+
+    #[test]
+    fn parses_pdf_dates() {
+        assert_eq!(Date::parse("D:20240131093005+01'30'"), Some(Date::new(2024, 1, 31, 9, 30, 5, 90)));
+        assert_eq!(Date::parse("D:19991231235959-08'00'"), Some(Date::new(1999, 12, 31, 23, 59, 59, -480)));
+        assert_eq!(Date::parse("D:2024Z"), Some(Date::new(2024, 1, 1, 0, 0, 0, 0)));
+        assert_eq!(Date::parse("D:202"), None);
+        assert_eq!(Date::parse("D:20240131+"), None);
+    }
+}
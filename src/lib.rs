@@ -1,17 +1,58 @@
 ﻿extern crate libharu_sys;
 extern crate libc;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
 
+#[macro_use]
+mod logging;
+
+mod base_font;
+mod cjk;
+mod compression;
+mod destination;
 mod document;
 mod document_inner;
+mod encoding;
+mod encryption;
 mod error;
 mod font;
+mod graphics_state;
+mod image;
+mod info;
+mod matrix;
+mod outline;
 mod page;
 mod page_layout;
+mod page_mode;
+mod paragraph;
+mod reader_stream;
+mod rich_text;
+mod text_object;
+mod ttc;
 
+pub use base_font::{BaseFont, BuiltinFont};
+pub use cjk::{CidFont, CjkLanguage};
+pub use compression::Compression;
+pub use destination::Destination;
 pub use document::Document;
+pub use encoding::Encoding;
+pub use encryption::{EncryptionMode, Permissions};
 pub use error::{Code, Error, Result};
-pub use font::Font;
-pub use page::{Page, LineCap, LineJoin, Point, Size};
+pub use font::{Font, Measurement};
+pub use graphics_state::GraphicsState;
+pub use image::{ColorSpace, Image};
+pub use info::{Date, InfoAttribute};
+pub use matrix::Matrix;
+pub use outline::Outline;
+pub use page::{Page, Color, LineCap, LineJoin, Point, Rect, Size, TextAlignment, TextRenderingMode};
+pub use page_mode::PageMode;
+pub use paragraph::{Paragraph, Line, break_opportunities};
+pub use rich_text::{RichText, RichLine, Run, Span};
+pub use text_object::TextObject;
+pub use ttc::{TtcFace, list_ttc_faces};
 
 
 #[test]
@@ -0,0 +1,16 @@
+//! Internal logging macros. With the `log` feature these forward to the `log` crate under the
+//! `libharu` target; without it they compile to nothing, so the library never prints on its own.
+
+#[cfg(feature = "log")]
+macro_rules! haru_log {
+    ($level:ident, $($arg:tt)*) => { $level!(target: "libharu", $($arg)*) }
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! haru_log {
+    ($level:ident, $($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } }
+}
+
+macro_rules! haru_trace { ($($arg:tt)*) => { haru_log!(trace, $($arg)*) } }
+macro_rules! haru_debug { ($($arg:tt)*) => { haru_log!(debug, $($arg)*) } }
+macro_rules! haru_warn { ($($arg:tt)*) => { haru_log!(warn, $($arg)*) } }
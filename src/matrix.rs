@@ -0,0 +1,106 @@
+use libharu_sys as haru;
+use std::f32::consts::PI;
+
+use page::{Point, Size};
+
+/// An affine transformation `[a b c d x y]`, mapping `(px, py)` to
+/// `(a * px + c * py + x, b * px + d * py + y)`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Matrix {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Matrix {
+    pub fn new(a: f32, b: f32, c: f32, d: f32, x: f32, y: f32) -> Matrix {
+        Matrix{ a: a, b: b, c: c, d: d, x: x, y: y }
+    }
+
+    pub fn identity() -> Matrix {
+        Matrix::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    pub fn translation(dx: f32, dy: f32) -> Matrix {
+        Matrix::new(1.0, 0.0, 0.0, 1.0, dx, dy)
+    }
+
+    pub fn scaling(sx: f32, sy: f32) -> Matrix {
+        Matrix::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+    }
+
+    /// A counterclockwise rotation about the origin.
+    pub fn rotation_degrees(angle: f32) -> Matrix {
+        let (sin, cos) = (angle * PI / 180.0).sin_cos();
+        Matrix::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    pub fn from_haru(m: haru::HPDF_TransMatrix) -> Matrix {
+        Matrix::new(m.a, m.b, m.c, m.d, m.x, m.y)
+    }
+
+    /// The transformation that applies `self`, then `next`.
+    pub fn then(&self, next: &Matrix) -> Matrix {
+        Matrix{
+            a: self.a * next.a + self.b * next.c,
+            b: self.a * next.b + self.b * next.d,
+            c: self.c * next.a + self.d * next.c,
+            d: self.c * next.b + self.d * next.d,
+            x: self.x * next.a + self.y * next.c + next.x,
+            y: self.x * next.b + self.y * next.d + next.y,
+        }
+    }
+
+    /// The transformation that undoes this one, or `None` if it collapses the plane onto a line or point.
+    pub fn invert(&self) -> Option<Matrix> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Matrix{
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            x: (self.c * self.y - self.d * self.x) / det,
+            y: (self.b * self.x - self.a * self.y) / det,
+        })
+    }
+
+    pub fn apply(&self, point: Point) -> Point {
+        Point::new(self.a * point.x + self.c * point.y + self.x, self.b * point.x + self.d * point.y + self.y)
+    }
+
+    /// Transforms a distance, which unlike a point is not translated.
+    pub fn apply_size(&self, size: Size) -> Size {
+        Size::new(self.a * size.width + self.c * size.height, self.b * size.width + self.d * size.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Matrix;
+    use page::Point;
+
+    fn assert_close(actual: Point, expected: Point) {
+        assert!((actual.x - expected.x).abs() < 1e-4 && (actual.y - expected.y).abs() < 1e-4,
+            "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn composes_in_order() {
+        let m = Matrix::rotation_degrees(90.0).then(&Matrix::translation(10.0, 0.0));
+        assert_close(m.apply(Point::new(1.0, 0.0)), Point::new(10.0, 1.0));
+    }
+
+    #[test]
+    fn inverts() {
+        let m = Matrix::scaling(2.0, 3.0).then(&Matrix::rotation_degrees(30.0)).then(&Matrix::translation(5.0, -7.0));
+        let p = Point::new(3.0, 4.0);
+        assert_close(m.invert().unwrap().apply(m.apply(p)), p);
+        assert_eq!(Matrix::scaling(0.0, 1.0).invert(), None);
+    }
+}
@@ -0,0 +1,34 @@
+use libharu_sys as haru;
+use document_inner::DocumentInner;
+use std::rc::Rc;
+
+use destination::Destination;
+use error::{Error, Result};
+
+/// An entry in the document's outline, the tree of bookmarks that viewers show beside the pages.
+/// Created by `Document::create_outline`.
+#[derive(Clone)]
+pub struct Outline {
+    handle: haru::HPDF_Outline,
+    doc: Rc<DocumentInner>
+}
+
+impl Outline {
+    pub fn from_handle(handle: haru::HPDF_Outline, doc: Rc<DocumentInner>) -> Outline {
+        Outline{ handle: handle, doc: doc }
+    }
+    
+    pub unsafe fn get_handle(&self) -> haru::HPDF_Outline {
+        self.handle
+    }
+    
+    /// Whether the entry's children are shown when the document is opened.
+    pub fn set_opened(&mut self, opened: bool) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Outline_SetOpened(self.handle, opened as haru::HPDF_BOOL) } )
+    }
+    
+    /// Where the viewer goes when the entry is clicked.
+    pub fn set_destination(&mut self, destination: &Destination) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Outline_SetDestination(self.handle, destination.get_handle()) } )
+    }
+}
@@ -1,15 +1,19 @@
 
 use document_inner::DocumentInner;
 use libharu_sys as haru;
-use std::ffi::CString;
 use std::rc::Rc;
 
+use destination::Destination;
 use error::{Error, Result};
 use font::Font;
+use graphics_state::GraphicsState;
+use image::Image;
+use matrix::Matrix;
+use text_object::TextObject;
 
 pub struct Page {
     handle: haru::HPDF_Page,
-    _doc: Rc<DocumentInner>
+    doc: Rc<DocumentInner>
 }
 
 pub enum LineCap {
@@ -24,6 +28,53 @@ pub enum LineJoin {
     Bevel = haru::HPDF_BEVEL_JOIN as isize,
 }
 
+/// Whether glyphs are filled, stroked, both or neither, and whether they are added to the clipping path.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextRenderingMode {
+    Fill = haru::HPDF_FILL as isize,
+    Stroke = haru::HPDF_STROKE as isize,
+    FillThenStroke = haru::HPDF_FILL_THEN_STROKE as isize,
+    Invisible = haru::HPDF_INVISIBLE as isize,
+    FillClipping = haru::HPDF_FILL_CLIPPING as isize,
+    StrokeClipping = haru::HPDF_STROKE_CLIPPING as isize,
+    FillStrokeClipping = haru::HPDF_FILL_STROKE_CLIPPING as isize,
+    Clipping = haru::HPDF_CLIPPING as isize,
+}
+
+impl TextRenderingMode {
+    pub fn from_int(mode: haru::HPDF_TextRenderingMode) -> Option<TextRenderingMode> {
+        Some(match mode {
+            haru::HPDF_FILL => TextRenderingMode::Fill,
+            haru::HPDF_STROKE => TextRenderingMode::Stroke,
+            haru::HPDF_FILL_THEN_STROKE => TextRenderingMode::FillThenStroke,
+            haru::HPDF_INVISIBLE => TextRenderingMode::Invisible,
+            haru::HPDF_FILL_CLIPPING => TextRenderingMode::FillClipping,
+            haru::HPDF_STROKE_CLIPPING => TextRenderingMode::StrokeClipping,
+            haru::HPDF_FILL_STROKE_CLIPPING => TextRenderingMode::FillStrokeClipping,
+            haru::HPDF_CLIPPING => TextRenderingMode::Clipping,
+            _ => { return None; }
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextAlignment {
+    Left = haru::HPDF_TALIGN_LEFT as isize,
+    Right = haru::HPDF_TALIGN_RIGHT as isize,
+    Center = haru::HPDF_TALIGN_CENTER as isize,
+    
+    /// Stretch the spaces between words so that each line but the last fills the width.
+    Justify = haru::HPDF_TALIGN_JUSTIFY as isize,
+}
+
+/// A color in one of the device color spaces. Components range from 0 to 1.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Color {
+    Gray(f32),
+    Rgb(f32, f32, f32),
+    Cmyk(f32, f32, f32, f32),
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Point {
     pub x: f32,
@@ -48,42 +99,99 @@ impl Size {
     }
 }
 
+#[derive(Debug, Copy, Clone)]
+pub struct Rect {
+    pub left: f32,
+    pub bottom: f32,
+    pub right: f32,
+    pub top: f32,
+}
+
+impl Rect {
+    pub fn new(lower_left: Point, size: Size) -> Rect {
+        Rect{
+            left: lower_left.x,
+            bottom: lower_left.y,
+            right: lower_left.x + size.width,
+            top: lower_left.y + size.height,
+        }
+    }
+    
+    pub fn from_edges(left: f32, bottom: f32, right: f32, top: f32) -> Rect {
+        Rect{ left: left, bottom: bottom, right: right, top: top }
+    }
+    
+    pub fn lower_left(&self) -> Point {
+        Point::new(self.left, self.bottom)
+    }
+    
+    pub fn upper_left(&self) -> Point {
+        Point::new(self.left, self.top)
+    }
+    
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+    
+    pub fn height(&self) -> f32 {
+        self.top - self.bottom
+    }
+    
+    pub fn size(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+}
+
 
 impl Page {
     pub fn from_handle(handle: haru::HPDF_Page, doc: Rc<DocumentInner>) -> Page {
-        Page{ handle: handle, _doc: doc }
+        Page{ handle: handle, doc: doc }
     }
     
     pub unsafe fn get_handle(&self) -> haru::HPDF_Page {
         self.handle
     }
     
+    /// The document the page belongs to.
+    pub unsafe fn get_doc_handle(&self) -> haru::HPDF_Doc {
+        self.doc.handle
+    }
+    
+    /// A destination showing all of this page. `Destination` has constructors for other views.
+    pub fn create_destination(&self) -> Result<Destination> {
+        let dest = unsafe { haru::HPDF_Page_CreateDestination(self.handle) };
+        if dest.is_null() {
+            return Error::last_err(self.doc.handle);
+        }
+        Ok(Destination::from_handle(dest, self.doc.clone()))
+    }
+    
     pub fn set_width(&mut self, width: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetWidth(self.handle, width) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetWidth(self.handle, width) })
     }
     
     pub fn set_height(&mut self, height: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetHeight(self.handle, height) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetHeight(self.handle, height) })
     }
     
     pub fn set_line_width(&mut self, line_width: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetLineWidth(self.handle, line_width) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetLineWidth(self.handle, line_width) })
     }
     
     pub fn set_line_cap(&mut self, line_cap: LineCap) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetLineCap(self.handle, line_cap as haru::HPDF_LineCap) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetLineCap(self.handle, line_cap as haru::HPDF_LineCap) })
     }
     
     pub fn set_line_join(&mut self, line_join: LineJoin) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetLineJoin(self.handle, line_join as haru::HPDF_LineJoin) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetLineJoin(self.handle, line_join as haru::HPDF_LineJoin) })
     }
     
     pub fn set_miter_limit(&mut self, miter_limit: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetMiterLimit(self.handle, miter_limit) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetMiterLimit(self.handle, miter_limit) })
     }
     
     pub fn set_dash(&mut self, dash_pattern: &[u16], phase: u32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetDash(
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetDash(
             self.handle, 
             dash_pattern.as_ptr(), 
             dash_pattern.len() as u32, 
@@ -92,114 +200,265 @@ impl Page {
     }
     
     pub fn set_flat(&mut self, flatness: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetFlat(self.handle, flatness) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetFlat(self.handle, flatness) })
     }
     
     // Color operators
     
     pub fn set_gray_fill(&mut self, gray: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetGrayFill(self.handle, gray) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetGrayFill(self.handle, gray) })
     }
     
     pub fn set_gray_stroke(&mut self, gray: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetGrayStroke(self.handle, gray) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetGrayStroke(self.handle, gray) })
     }
     
     pub fn set_rgb_fill(&mut self, r: f32, g: f32, b: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetRGBFill(self.handle, r, g, b) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetRGBFill(self.handle, r, g, b) })
+    }
+    
+    pub fn set_rgb_stroke(&mut self, r: f32, g: f32, b: f32) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetRGBStroke(self.handle, r, g, b) })
     }
     
     pub fn set_cmyk_stroke(&mut self, c: f32, m: f32, y: f32, k: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetCMYKStroke(self.handle, c, m, y, k) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetCMYKStroke(self.handle, c, m, y, k) })
     }
     
     pub fn set_cmyk_fill(&mut self, c: f32, m: f32, y: f32, k: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetCMYKFill(self.handle, c, m, y, k) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetCMYKFill(self.handle, c, m, y, k) })
+    }
+    
+    pub fn set_fill_color(&mut self, color: Color) -> Result<()> {
+        match color {
+            Color::Gray(gray) => self.set_gray_fill(gray),
+            Color::Rgb(r, g, b) => self.set_rgb_fill(r, g, b),
+            Color::Cmyk(c, m, y, k) => self.set_cmyk_fill(c, m, y, k),
+        }
+    }
+    
+    pub fn set_stroke_color(&mut self, color: Color) -> Result<()> {
+        match color {
+            Color::Gray(gray) => self.set_gray_stroke(gray),
+            Color::Rgb(r, g, b) => self.set_rgb_stroke(r, g, b),
+            Color::Cmyk(c, m, y, k) => self.set_cmyk_stroke(c, m, y, k),
+        }
     }
     
     pub fn move_to(&mut self, point: Point) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_MoveTo(self.handle, point.x, point.y) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_MoveTo(self.handle, point.x, point.y) })
     }
     
     pub fn line_to(&mut self, end: Point) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_LineTo(self.handle, end.x, end.y) })
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_LineTo(self.handle, end.x, end.y) })
     }
     
     pub fn curve_to(&mut self, outbound_control: Point, inbound_control: Point, end: Point) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_CurveTo(self.handle, 
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_CurveTo(self.handle, 
             outbound_control.x, outbound_control.y, inbound_control.x, inbound_control.y, end.x, end.y)})
     }
     
     pub fn curve_to_2(&mut self, inbound_control: Point, end: Point) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_CurveTo2(self.handle, 
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_CurveTo2(self.handle, 
             inbound_control.x, inbound_control.y, end.x, end.y)})
     }
     
     pub fn curve_to_3(&mut self, outbound_control: Point, end: Point) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_CurveTo3(self.handle, 
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_CurveTo3(self.handle, 
             outbound_control.x, outbound_control.y, end.x, end.y)})
     }
     
     pub fn arc(&mut self, center: Point, ray_endpoint: Point, angle_degrees: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_Arc(self.handle, 
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Arc(self.handle, 
             center.x, center.y, ray_endpoint.x, ray_endpoint.y, angle_degrees)})
     }
     pub fn circle(&mut self, center: Point, radius: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_Circle(
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Circle(
             self.handle, center.x, center.y, radius)})
     }
     
     pub fn close_path(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_ClosePath(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_ClosePath(self.handle) } )
     }
     
     pub fn rectangle(&mut self, lower_left: Point, size: Size) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_Rectangle(self.handle,
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Rectangle(self.handle,
             lower_left.x, lower_left.y, size.width, size.height) } )
     }
     
     pub fn stroke(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_Stroke(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Stroke(self.handle) } )
     }
     pub fn close_path_stroke(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_ClosePathStroke(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_ClosePathStroke(self.handle) } )
     }
     pub fn fill(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_Fill(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Fill(self.handle) } )
     }
     pub fn eofill(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_Eofill(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Eofill(self.handle) } )
     }
     pub fn fill_stroke(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_FillStroke(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_FillStroke(self.handle) } )
     }
     pub fn eofill_stroke(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_EofillStroke(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_EofillStroke(self.handle) } )
     }
     pub fn close_path_fill_stroke(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_ClosePathFillStroke(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_ClosePathFillStroke(self.handle) } )
     }
     pub fn close_path_eofill_stroke(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_ClosePathEofillStroke(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_ClosePathEofillStroke(self.handle) } )
     }
     pub fn end_path(&mut self) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_EndPath(self.handle) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_EndPath(self.handle) } )
+    }
+
+    /// Intersects the clipping path with the current path, using the nonzero winding rule.
+    /// Takes effect once the path is painted or ended with `end_path`, and lasts until the graphics
+    /// state is restored, so clip inside `save_state`:
+    ///
+    /// ```ignore
+    /// let mut state = try!(page.save_state());
+    /// try!(state.circle(center, radius));
+    /// try!(state.clip());
+    /// try!(state.end_path());
+    /// // draw inside the circle
+    /// try!(state.restore());
+    /// ```
+    pub fn clip(&mut self) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Clip(self.handle) } )
+    }
+
+    /// Like `clip`, but using the even-odd rule, so that overlapping subpaths cut holes.
+    pub fn eoclip(&mut self) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Eoclip(self.handle) } )
+    }
+    
+    /// Draws `image` stretched to fill `rect`.
+    pub fn draw_image(&mut self, image: &Image, rect: Rect) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_DrawImage(self.handle, image.get_handle(),
+            rect.left, rect.bottom, rect.width(), rect.height()) } )
     }
     
     pub fn set_font_and_size(&mut self, font: &Font, size: f32) -> Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_SetFontAndSize(self.handle, font.get_handle(), size) } )
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetFontAndSize(self.handle, font.get_handle(), size) } )
+    }
+    
+    /// Begins a text object, in which text can be shown. The text object ends when the returned
+    /// `TextObject` is dropped or `TextObject::end` is called.
+    pub fn begin_text(&mut self) -> Result<TextObject> {
+        try!(Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_BeginText(self.handle) } ));
+        Ok(TextObject::new(self))
+    }
+    
+    /// Runs `f` inside a text object.
+    pub fn text<R, F: FnOnce(&mut TextObject) -> Result<R>>(&mut self, f: F) -> Result<R> {
+        let mut text = try!(self.begin_text());
+        let ret = try!(f(&mut text));
+        try!(text.end());
+        Ok(ret)
+    }
+    
+    /// Saves the graphics state: the transformation, clipping path, colors, line styles and text state.
+    /// It is restored when the returned `GraphicsState` is dropped or `GraphicsState::restore` is called.
+    pub fn save_state(&mut self) -> Result<GraphicsState> {
+        try!(Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_GSave(self.handle) } ));
+        Ok(GraphicsState::new(self))
+    }
+    
+    // Coordinate transforms. These change the current transformation until the graphics state is restored.
+    
+    /// Applies `matrix` to everything drawn afterwards, before the current transformation.
+    pub fn concat(&mut self, matrix: Matrix) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_Concat(self.handle,
+            matrix.a, matrix.b, matrix.c, matrix.d, matrix.x, matrix.y) } )
+    }
+    
+    pub fn translate(&mut self, dx: f32, dy: f32) -> Result<()> {
+        self.concat(Matrix::translation(dx, dy))
+    }
+    
+    /// Rotates counterclockwise about the current origin.
+    pub fn rotate_degrees(&mut self, angle: f32) -> Result<()> {
+        self.concat(Matrix::rotation_degrees(angle))
     }
     
+    pub fn scale(&mut self, sx: f32, sy: f32) -> Result<()> {
+        self.concat(Matrix::scaling(sx, sy))
+    }
     
-    pub fn begin_text(&mut self) ->  Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_BeginText(self.handle) } )
+    /// The current transformation, from user space to default page space.
+    pub fn current_transform(&self) -> Matrix {
+        Matrix::from_haru( unsafe { haru::HPDF_Page_GetTransMatrix(self.handle) } )
     }
-    pub fn end_text(&mut self) ->  Result<()> {
-        Error::from_status( unsafe { haru::HPDF_Page_EndText(self.handle) } )
+    
+    // Text state operators. These may be used inside or outside a text object.
+    
+    /// Extra space added after every character, in unscaled text space units.
+    pub fn set_char_space(&mut self, char_space: f32) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetCharSpace(self.handle, char_space) } )
     }
-    pub fn text_out(&mut self, baseline_left: Point, text: &str) ->  Result<()> {
-        let chrs = try!(CString::new(text));
-        Error::from_status( unsafe { haru::HPDF_Page_TextOut(self.handle, baseline_left.x, baseline_left.y, chrs.as_ptr()) } )
+    
+    /// Extra space added after every space character, in unscaled text space units.
+    pub fn set_word_space(&mut self, word_space: f32) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetWordSpace(self.handle, word_space) } )
+    }
+    
+    /// Horizontal stretching of text, as a percentage. 100 is normal.
+    pub fn set_horizontal_scaling(&mut self, scaling: f32) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetHorizontalScalling(self.handle, scaling) } )
+    }
+    
+    /// The distance between baselines used by `move_to_next_line` and `show_text_next_line`.
+    pub fn set_text_leading(&mut self, leading: f32) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetTextLeading(self.handle, leading) } )
     }
     
-}
\ No newline at end of file
+    pub fn set_text_rendering_mode(&mut self, mode: TextRenderingMode) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetTextRenderingMode(self.handle, mode as haru::HPDF_TextRenderingMode) } )
+    }
+    
+    /// Raises (or, if negative, lowers) text above the baseline, as for superscripts.
+    pub fn set_text_rise(&mut self, rise: f32) -> Result<()> {
+        Error::from_doc_status(self.doc.handle, unsafe { haru::HPDF_Page_SetTextRise(self.handle, rise) } )
+    }
+    
+    pub fn char_space(&self) -> f32 {
+        unsafe { haru::HPDF_Page_GetCharSpace(self.handle) }
+    }
+    
+    pub fn word_space(&self) -> f32 {
+        unsafe { haru::HPDF_Page_GetWordSpace(self.handle) }
+    }
+    
+    pub fn horizontal_scaling(&self) -> f32 {
+        unsafe { haru::HPDF_Page_GetHorizontalScalling(self.handle) }
+    }
+    
+    pub fn text_leading(&self) -> f32 {
+        unsafe { haru::HPDF_Page_GetTextLeading(self.handle) }
+    }
+    
+    pub fn text_rendering_mode(&self) -> Option<TextRenderingMode> {
+        TextRenderingMode::from_int( unsafe { haru::HPDF_Page_GetTextRenderingMode(self.handle) } )
+    }
+    
+    pub fn text_rise(&self) -> f32 {
+        unsafe { haru::HPDF_Page_GetTextRise(self.handle) }
+    }
+    
+    pub fn current_font(&self) -> Option<Font> {
+        let font = unsafe { haru::HPDF_Page_GetCurrentFont(self.handle) };
+        if font.is_null() {
+            None
+        } else {
+            Some(Font::from_handle(font, self.doc.clone()))
+        }
+    }
+    
+    pub fn current_font_size(&self) -> f32 {
+        unsafe { haru::HPDF_Page_GetCurrentFontSize(self.handle) }
+    }
+    
+}
@@ -0,0 +1,37 @@
+use libharu_sys as haru;
+
+/// Describes what a viewer shows beside the pages when the document is opened.
+pub enum PageMode {
+    /// Only the pages.
+    UseNone,
+    
+    /// The outline, as well as the pages.
+    UseOutlines,
+    
+    /// Page thumbnails, as well as the pages.
+    UseThumbs,
+    
+    /// The pages, filling the screen without menus or windows.
+    FullScreen,
+}
+
+impl PageMode {
+    pub fn as_int(&self) -> haru::HPDF_PageMode {
+        match *self {
+            PageMode::UseNone => haru::HPDF_PAGE_MODE_USE_NONE,
+            PageMode::UseOutlines => haru::HPDF_PAGE_MODE_USE_OUTLINE,
+            PageMode::UseThumbs => haru::HPDF_PAGE_MODE_USE_THUMBS,
+            PageMode::FullScreen => haru::HPDF_PAGE_MODE_FULL_SCREEN,
+        }
+    }
+    
+    pub fn from_int(mode: haru::HPDF_PageMode) -> Option<PageMode> {
+        Some(match mode {
+            haru::HPDF_PAGE_MODE_USE_NONE => PageMode::UseNone,
+            haru::HPDF_PAGE_MODE_USE_OUTLINE => PageMode::UseOutlines,
+            haru::HPDF_PAGE_MODE_USE_THUMBS => PageMode::UseThumbs,
+            haru::HPDF_PAGE_MODE_FULL_SCREEN => PageMode::FullScreen,
+            _ => { return None; } // probably HPDF_PAGE_MODE_EOF
+        })
+    }
+}
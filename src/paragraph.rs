@@ -0,0 +1,339 @@
+use error::Result;
+use font::Font;
+use page::{Page, Point, Rect, TextAlignment};
+use text_object::TextObject;
+
+/// Lays out text in a single font and size across as many lines as it needs, breaking lines at spaces,
+/// after hyphens and between CJK characters.
+pub struct Paragraph {
+    pub font: Font,
+    pub size: f32,
+
+    /// The distance between the baselines of consecutive lines.
+    pub leading: f32,
+
+    pub align: TextAlignment,
+}
+
+/// One line of a `Paragraph`, as produced by `Paragraph::layout`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line<'t> {
+    /// The text of the line, without the whitespace it was broken at.
+    pub text: &'t str,
+
+    /// The byte offset in the laid out text where the next line starts.
+    pub end: usize,
+
+    /// The width of `text`, in points.
+    pub width: f32,
+
+    /// Whether this line ends at a newline or at the end of the text. Such lines are not justified.
+    pub ends_paragraph: bool,
+}
+
+impl Paragraph {
+    /// A left-aligned paragraph whose leading is the font's line height.
+    pub fn new(font: &Font, size: f32) -> Paragraph {
+        Paragraph{
+            font: font.clone(),
+            size: size,
+            leading: font.line_height(size),
+            align: TextAlignment::Left,
+        }
+    }
+
+    /// Breaks `text` into lines no wider than `width`. A word too wide for a line by itself is broken
+    /// between characters.
+    pub fn layout<'t>(&self, text: &'t str, width: f32) -> Vec<Line<'t>> {
+        let mut lines = Vec::new();
+        let mut paragraph_start = 0;
+        for paragraph in text.split('\n') {
+            let paragraph_end = paragraph_start + paragraph.trim_end_matches('\r').len();
+            self.layout_paragraph(text, paragraph_start, paragraph_end, width, &mut lines);
+
+            paragraph_start += paragraph.len() + 1;
+            if let Some(last) = lines.last_mut() {
+                last.end = paragraph_start.min(text.len());
+            }
+        }
+        lines
+    }
+
+    fn layout_paragraph<'t>(&self, text: &'t str, start: usize, end: usize, width: f32, lines: &mut Vec<Line<'t>>) {
+        let mut line_start = start;
+        let mut line_word_end = start;
+        let mut line_width = 0.0;
+        let mut pending_space = 0.0;
+
+        let mut segment_start = start;
+        for segment_end in break_opportunities(&text[start..end]).into_iter().map(|offset| start + offset) {
+            let segment = &text[segment_start..segment_end];
+            let word_end = segment_start + segment.trim_end().len();
+            let word_width = self.width(&text[segment_start..word_end]);
+            let segment_width = self.width(segment);
+
+            if line_word_end > line_start && line_width + pending_space + word_width > width {
+                lines.push(Line{
+                    text: &text[line_start..line_word_end],
+                    end: segment_start,
+                    width: line_width,
+                    ends_paragraph: false,
+                });
+                line_start = segment_start;
+                line_word_end = segment_start;
+                line_width = 0.0;
+                pending_space = 0.0;
+            }
+
+            if line_word_end == line_start && word_width > width {
+                // Nowhere to break the word, so break it wherever it stops fitting.
+                let mut rest_start = line_start;
+                loop {
+                    let rest = &text[rest_start..word_end];
+                    let mut fit = self.font.measure(rest, self.size, width, 0.0, 0.0, false).bytes_fit;
+                    if fit == 0 {
+                        fit = rest.chars().next().map(|c| c.len_utf8()).unwrap_or(rest.len());
+                    }
+                    if fit >= rest.len() {
+                        line_start = rest_start;
+                        line_width = self.width(rest);
+                        break;
+                    }
+
+                    lines.push(Line{
+                        text: &rest[..fit],
+                        end: rest_start + fit,
+                        width: self.width(&rest[..fit]),
+                        ends_paragraph: false,
+                    });
+                    rest_start += fit;
+                }
+            } else {
+                line_width += pending_space + word_width;
+            }
+            line_word_end = word_end;
+            pending_space = segment_width - word_width;
+            segment_start = segment_end;
+        }
+
+        lines.push(Line{
+            text: &text[line_start..line_word_end],
+            end: end,
+            width: line_width,
+            ends_paragraph: true,
+        });
+    }
+
+    fn width(&self, text: &str) -> f32 {
+        if text.is_empty() {
+            0.0
+        } else {
+            self.font.measure_width(text, self.size)
+        }
+    }
+
+    /// Draws as much of `text` as fits in `rect`, starting at the top. Returns the text that did not fit,
+    /// which is empty if all of it did, so that it can be continued elsewhere, such as on the next page.
+    pub fn draw<'t>(&self, page: &mut Page, rect: Rect, text: &'t str) -> Result<&'t str> {
+        let lines = self.layout(text, rect.width());
+        let descent = self.font.get_descent() * self.size;
+
+        let mut text_object = try!(page.begin_text());
+        try!(text_object.set_font_and_size(&self.font, self.size));
+
+        let mut baseline = rect.top - self.font.get_ascent() * self.size;
+        let mut consumed = 0;
+        for line in &lines {
+            if baseline + descent < rect.bottom - 0.001 {
+                break;
+            }
+            try!(self.draw_line(&mut text_object, rect, baseline, line));
+            consumed = line.end;
+            baseline -= self.leading;
+        }
+
+        try!(text_object.end());
+        Ok(&text[consumed..])
+    }
+
+    fn draw_line(&self, text_object: &mut TextObject, rect: Rect, baseline: f32, line: &Line) -> Result<()> {
+        if line.text.is_empty() {
+            return Ok( () );
+        }
+
+        let x = match self.align {
+            TextAlignment::Left | TextAlignment::Justify => rect.left,
+            TextAlignment::Right => rect.right - line.width,
+            TextAlignment::Center => rect.left + (rect.width() - line.width) / 2.0,
+        };
+
+        if self.align == TextAlignment::Justify && !line.ends_paragraph {
+            let words: Vec<&str> = line.text.split(' ').filter(|word| !word.is_empty()).collect();
+            if words.len() > 1 {
+                let widths: Vec<f32> = words.iter().map(|word| self.width(word)).collect();
+                let gap = (rect.width() - widths.iter().fold(0.0, |sum, width| sum + width)) / (words.len() - 1) as f32;
+
+                let mut x = rect.left;
+                for (word, width) in words.iter().zip(widths) {
+                    try!(text_object.text_out(Point::new(x, baseline), word));
+                    x += width + gap;
+                }
+                return Ok( () );
+            }
+        }
+
+        text_object.text_out(Point::new(x, baseline), line.text)
+    }
+}
+
+/// The byte offsets in `text` where a line may start, followed by `text.len()`.
+/// This is a simplification of the Unicode line breaking algorithm that covers Latin and CJK text.
+pub fn break_opportunities(text: &str) -> Vec<usize> {
+    let mut breaks = Vec::new();
+    let mut prev = None;
+    for (i, c) in text.char_indices() {
+        if let Some(before) = prev {
+            if can_break_between(before, c) {
+                breaks.push(i);
+            }
+        }
+        prev = Some(c);
+    }
+    breaks.push(text.len());
+    breaks
+}
+
+/// Whether a line may break between two adjacent characters.
+pub fn can_break_between(before: char, after: char) -> bool {
+    if after.is_whitespace() {
+        // Spaces stay at the end of the line they follow.
+        return false;
+    }
+    if before == '\u{200B}' {
+        // zero width space
+        return true;
+    }
+    if before.is_whitespace() {
+        return before != '\u{A0}' && before != '\u{202F}'; // no-break spaces
+    }
+    if (before == '-' || before == '\u{2010}') && after.is_alphabetic() {
+        return true;
+    }
+    if is_cjk(before) || is_cjk(after) {
+        return !is_opening_punctuation(before) && !is_closing_punctuation(after);
+    }
+    false
+}
+
+fn is_cjk(c: char) -> bool {
+    match c as u32 {
+        0x3000..=0x303F | // CJK Symbols and Punctuation
+        0x3040..=0x30FF | // Hiragana, Katakana
+        0x3400..=0x4DBF | // CJK Unified Ideographs Extension A
+        0x4E00..=0x9FFF | // CJK Unified Ideographs
+        0xAC00..=0xD7AF | // Hangul Syllables
+        0xF900..=0xFAFF | // CJK Compatibility Ideographs
+        0xFF00..=0xFFEF | // Halfwidth and Fullwidth Forms
+        0x20000..=0x2FFFF => true, // Supplementary Ideographic Plane
+        _ => false,
+    }
+}
+
+fn is_opening_punctuation(c: char) -> bool {
+    "([{\u{2018}\u{201C}\u{3008}\u{300A}\u{300C}\u{300E}\u{3010}\u{3014}\u{FF08}".contains(c)
+}
+
+fn is_closing_punctuation(c: char) -> bool {
+    ")]}.,:;!?\u{2019}\u{201D}\u{3001}\u{3002}\u{3009}\u{300B}\u{300D}\u{300F}\u{3011}\u{3015}\u{FF09}\u{FF0C}\u{FF0E}\u{FF1A}\u{FF1B}\u{FF01}\u{FF1F}\u{30FC}\u{3005}\u{3041}\u{3043}\u{3045}\u{3047}\u{3049}\u{3063}\u{3083}\u{3085}\u{3087}\u{30A1}\u{30A3}\u{30A5}\u{30A7}\u{30A9}\u{30C3}\u{30E3}\u{30E5}\u{30E7}".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Paragraph, break_opportunities};
+    use base_font::BaseFont;
+    use document::Document;
+    use encoding::Encoding;
+    use page::{Rect, TextAlignment};
+
+    fn lines_starting_at(text: &str) -> Vec<&str> {
+        let mut start = 0;
+        break_opportunities(text).into_iter().map(|end| {
+            let segment = &text[start..end];
+            start = end;
+            segment
+        }).collect()
+    }
+
+    #[test]
+    fn breaks_after_spaces_and_hyphens() {
+        assert_eq!(lines_starting_at("a well-known  fact"), vec!["a ", "well-", "known  ", "fact"]);
+        assert_eq!(lines_starting_at("-5\u{A0}EUR"), vec!["-5\u{A0}EUR"]);
+    }
+
+    #[test]
+    fn breaks_between_ideographs_but_not_before_closing_punctuation() {
+        assert_eq!(lines_starting_at("漢字。「かな」"), vec!["漢", "字。", "「か", "な」"]);
+    }
+
+    #[test]
+    fn lays_out_words_within_the_width() {
+        let doc = Document::new().unwrap();
+        let font = doc.get_font(BaseFont::Helvetica, Encoding::WinAnsi).unwrap();
+        let paragraph = Paragraph::new(&font, 12.0);
+        let text = "the quick brown fox jumps over the lazy dog\nagain";
+        let width = font.measure_width("the quick brown", 12.0) + 1.0;
+
+        let lines = paragraph.layout(text, width);
+        let texts: Vec<&str> = lines.iter().map(|line| line.text).collect();
+        assert_eq!(texts, vec!["the quick brown", "fox jumps over", "the lazy dog", "again"]);
+        assert!(lines.iter().all(|line| line.width <= width));
+        let ends: Vec<bool> = lines.iter().map(|line| line.ends_paragraph).collect();
+        assert_eq!(ends, vec![false, false, true, true]);
+        assert_eq!(&text[lines[0].end..lines[1].end], "fox jumps over ");
+    }
+
+    #[test]
+    fn breaks_words_wider_than_a_line() {
+        let doc = Document::new().unwrap();
+        let font = doc.get_font(BaseFont::Courier, Encoding::WinAnsi).unwrap();
+        let paragraph = Paragraph::new(&font, 12.0);
+        let width = font.measure_width("abcde", 12.0) + 0.5;
+
+        let lines = paragraph.layout("ab abcdefghijklmnop", width);
+        let texts: Vec<&str> = lines.iter().map(|line| line.text).collect();
+        assert_eq!(texts, vec!["ab", "abcde", "fghij", "klmno", "p"]);
+        assert!(lines.iter().all(|line| line.width <= width));
+    }
+
+    #[test]
+    fn justifies_all_but_paragraph_ends() {
+        let doc = Document::new().unwrap();
+        let font = doc.get_font(BaseFont::Helvetica, Encoding::WinAnsi).unwrap();
+        let mut paragraph = Paragraph::new(&font, 12.0);
+        paragraph.align = TextAlignment::Justify;
+        let text = "one two three four\nfive";
+        let width = font.measure_width("one two three", 12.0) + 1.0;
+
+        let lines = paragraph.layout(text, width);
+        let ends: Vec<bool> = lines.iter().map(|line| line.ends_paragraph).collect();
+        assert_eq!(ends, vec![false, true, true]);
+
+        let mut page = doc.add_page().unwrap();
+        let rest = paragraph.draw(&mut page, Rect::from_edges(0.0, 0.0, width, 200.0), text).unwrap();
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn draws_what_fits_and_returns_the_rest() {
+        let doc = Document::new().unwrap();
+        let font = doc.get_font(BaseFont::Helvetica, Encoding::WinAnsi).unwrap();
+        let paragraph = Paragraph::new(&font, 10.0);
+        let text = "one two three four five six";
+        let width = font.measure_width("one two", 10.0) + 1.0;
+        let height = paragraph.leading * 2.5;
+
+        let mut page = doc.add_page().unwrap();
+        let rest = paragraph.draw(&mut page, Rect::from_edges(0.0, 100.0 - height, width, 100.0), text).unwrap();
+        assert_eq!(rest, "four five six");
+    }
+}
@@ -0,0 +1,148 @@
+use libharu_sys as haru;
+use std::cell::Cell;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::mem::{transmute, forget};
+use std::ops::DerefMut;
+use std::rc::Rc;
+use std::slice;
+
+/// A libharu stream that reads from a Rust `Read+Seek`.
+///
+/// Some libharu objects keep the stream they are handed (a TrueType font, for example) and may read
+/// from it again at save time. That is why the reader must be `'static`. Streams that libharu only
+/// reads during a call must be released with `free`.
+pub struct ReaderStream {
+    pub handle: haru::HPDF_Stream,
+    
+    // Shared with the reader, since the stream may be freed before the libharu call that consumes it returns.
+    error: Rc<Cell<Option<io::Error>>>,
+}
+
+struct TellingReader<R: Read+Seek> {
+    reader: R,
+    pos: u64,
+    error: Rc<Cell<Option<io::Error>>>,
+}
+
+extern "C" fn read<R: Read+Seek>(stream: haru::HPDF_Stream, ptr: *mut haru::HPDF_BYTE, size: *mut haru::HPDF_UINT) -> haru::HPDF_STATUS {
+    let r : &mut TellingReader<R> = unsafe { transmute( (*stream).attr ) };
+    let buf: &mut [u8] = unsafe { slice::from_raw_parts_mut(ptr, *size as usize) };
+    
+    let mut read_total = 0;
+    while read_total < buf.len() {
+        let read_len = match r.reader.read(&mut buf[read_total..]) {
+            Ok(0) => { 
+                unsafe { *size = read_total as haru::HPDF_UINT };
+                haru_trace!("reader stream: read to EOF at {} after {} of {} bytes", r.pos + read_total as u64, read_total, buf.len());
+                r.pos += read_total as u64;
+                return 0x1058 /* EOF */; 
+            }
+            Ok(read_len) => read_len,
+            Err(e) => { 
+                unsafe { *size = 0; }
+                haru_debug!("reader stream: read failed: {}", e);
+                r.error.set(Some(e));
+                return 0x1016; 
+            },
+        };
+        
+        read_total += read_len;
+    }
+    
+    r.pos += read_total as u64;
+    haru_trace!("reader stream: read {} bytes, now at {}", read_total, r.pos);
+    
+    return 0;
+}
+
+extern "C" fn seek<R: Read+Seek>(stream: haru::HPDF_Stream, pos: haru::HPDF_INT, mode: haru::HPDF_WhenceMode) -> haru::HPDF_STATUS {
+    let r : &mut TellingReader<R> = unsafe { transmute( (*stream).attr ) };
+    
+    let res = r.reader.seek(match mode {
+        haru::HPDF_SEEK_CUR => SeekFrom::Current(pos as i64),
+        haru::HPDF_SEEK_END => SeekFrom::End(pos as i64),
+        haru::HPDF_SEEK_SET | _ => SeekFrom::Start(pos as u64),
+    });
+    
+    match res {
+        Ok(sought_to) => {
+            r.pos = sought_to;
+            0
+        }
+        Err(e) => {
+            haru_debug!("reader stream: seek failed: {}", e);
+            r.error.set(Some(e));
+            0x1016
+        }
+    }
+}
+
+extern "C" fn tell<R: Read+Seek>(stream: haru::HPDF_Stream) -> haru::HPDF_INT32 {
+    let r : &mut TellingReader<R> = unsafe { transmute( (*stream).attr ) };
+    r.pos as haru::HPDF_INT32
+}
+
+extern "C" fn size<R: Read+Seek>(stream: haru::HPDF_Stream) -> haru::HPDF_UINT32 {
+    let r : &mut TellingReader<R> = unsafe { transmute( (*stream).attr ) };
+    let saved_pos = r.pos;
+    let ret = match r.reader.seek(SeekFrom::End(0)) {
+        Ok(end) => end,
+        Err(e) => {
+            r.error.set(Some(e));
+            0
+        }
+    };
+    if let Err(e) = r.reader.seek(SeekFrom::Start(saved_pos)) {
+        // Oops -- we failed to seek back to where we started.
+        r.error.set(Some(e));
+        return 0;
+    }
+    ret as haru::HPDF_UINT32
+}
+
+extern "C" fn free<R: Read+Seek>(stream: haru::HPDF_Stream) {
+    let r : Box<TellingReader<R>> = unsafe { transmute( (*stream).attr ) };
+    drop(r)
+}
+
+impl ReaderStream {
+    pub fn new<R: Read+Seek+'static>(doc: haru::HPDF_Doc, r: R) -> ReaderStream {
+        let mmgr = unsafe { haru::HPDF_GetMMgr(doc) };
+        
+        let error = Rc::new(Cell::new(None));
+        let mut tr = Box::new(TellingReader{
+            reader: r,
+            pos: 0,
+            error: error.clone(),
+        });
+        
+        let stream = unsafe { haru::HPDF_CallbackReader_New(
+            mmgr, 
+            Some(read::<R>),
+            Some(seek::<R>),
+            Some(tell::<R>),
+            Some(size::<R>),
+            Some(free::<R>),
+            tr.deref_mut() as *mut TellingReader<R> as *mut ::libc::c_void
+        ) };
+        
+        forget(tr);
+        
+        ReaderStream{ handle: stream, error: error }
+    }
+    
+    /// Frees a stream that was not handed over to libharu, or that libharu only read from during a call.
+    pub fn free(self) {
+        unsafe { haru::HPDF_Stream_Free(self.handle); }
+    }
+    
+    /// Where the stream keeps its I/O error, for checking after the stream has been handed to libharu.
+    pub fn error_cell(&self) -> Rc<Cell<Option<io::Error>>> {
+        self.error.clone()
+    }
+    
+    /// Takes the I/O error, if any, that made a read or seek on this stream fail.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error.take()
+    }
+}
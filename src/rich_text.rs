@@ -0,0 +1,450 @@
+use error::Result;
+use font::Font;
+use page::{Color, Page, Point, Rect, Size, TextAlignment};
+use paragraph::{break_opportunities, can_break_between};
+
+/// A run of text in one style.
+#[derive(Clone)]
+pub struct Span {
+    pub text: String,
+    pub font: Font,
+    pub size: f32,
+    pub color: Color,
+    pub underline: bool,
+    pub strike: bool,
+}
+
+impl Span {
+    /// A black, undecorated span.
+    pub fn new(text: &str, font: &Font, size: f32) -> Span {
+        Span{
+            text: text.to_string(),
+            font: font.clone(),
+            size: size,
+            color: Color::Gray(0.0),
+            underline: false,
+            strike: false,
+        }
+    }
+}
+
+/// Lays out spans of differently styled text as one paragraph. Spans share a baseline on each line,
+/// and lines are spaced by the largest ascent and descent on them.
+pub struct RichText {
+    pub spans: Vec<Span>,
+    pub align: TextAlignment,
+
+    /// Extra space between lines, in points.
+    pub line_gap: f32,
+}
+
+/// Part of one span placed on a `RichLine`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    /// The index of the span in `RichText::spans`.
+    pub span: usize,
+
+    /// The byte range of the span's text on this line.
+    pub start: usize,
+    pub end: usize,
+
+    /// The offset of the run from the start of the line, in points.
+    pub x: f32,
+    pub width: f32,
+
+    /// How many places to break the line come before this run. Justified lines spread their extra space over these.
+    pub gaps_before: usize,
+}
+
+/// One line of `RichText`, as produced by `RichText::layout`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichLine {
+    pub runs: Vec<Run>,
+    pub width: f32,
+    pub ascent: f32,
+
+    /// How far the line extends below its baseline. This is negative.
+    pub descent: f32,
+
+    /// Set on the last line before a newline and on the last line of all, which justification leaves ragged.
+    pub ends_paragraph: bool,
+
+    /// Where in the spans the line starts, as a span index and a byte offset into its text.
+    pub start: (usize, usize),
+}
+
+/// A piece of one span that cannot be broken across lines.
+struct Atom {
+    span: usize,
+    start: usize,
+    end: usize,
+    trimmed_end: usize,
+    width: f32,
+    trimmed_width: f32,
+}
+
+enum Item {
+    /// Atoms that must stay on the same line, such as a word that changes style partway through.
+    Word(Vec<Atom>),
+
+    /// A newline in the given span, at the given offset.
+    HardBreak(usize, usize),
+}
+
+impl RichText {
+    pub fn new(spans: Vec<Span>) -> RichText {
+        RichText{ spans: spans, align: TextAlignment::Left, line_gap: 0.0 }
+    }
+
+    fn items(&self) -> Vec<Item> {
+        let mut items = Vec::new();
+        let mut prev_char = None;
+        for (span_index, span) in self.spans.iter().enumerate() {
+            let mut piece_start = 0;
+            for (piece_index, piece) in span.text.split('\n').enumerate() {
+                if piece_index > 0 {
+                    items.push(Item::HardBreak(span_index, piece_start - 1));
+                    prev_char = None;
+                }
+
+                let piece_end = piece_start + piece.trim_end_matches('\r').len();
+                let mut segment_start = piece_start;
+                for segment_end in break_opportunities(&span.text[piece_start..piece_end]).into_iter().map(|offset| piece_start + offset) {
+                    let segment = &span.text[segment_start..segment_end];
+                    let first_char = match segment.chars().next() {
+                        Some(c) => c,
+                        None => { continue; }
+                    };
+
+                    let starts_word = segment_start > piece_start || match prev_char {
+                        Some(before) => can_break_between(before, first_char),
+                        None => true,
+                    };
+                    let trimmed_end = segment_start + segment.trim_end().len();
+                    let atom = Atom{
+                        span: span_index,
+                        start: segment_start,
+                        end: segment_end,
+                        trimmed_end: trimmed_end,
+                        width: width_of(span, segment),
+                        trimmed_width: width_of(span, &span.text[segment_start..trimmed_end]),
+                    };
+
+                    let joins_previous = !starts_word && match items.last() {
+                        Some(&Item::Word(_)) => true,
+                        _ => false,
+                    };
+                    if joins_previous {
+                        if let Some(&mut Item::Word(ref mut atoms)) = items.last_mut() {
+                            atoms.push(atom);
+                        }
+                    } else {
+                        items.push(Item::Word(vec![atom]));
+                    }
+
+                    prev_char = segment.chars().last();
+                    segment_start = segment_end;
+                }
+
+                piece_start += piece.len() + 1;
+            }
+        }
+        items
+    }
+
+    /// Breaks the spans into lines no wider than `width`. A word too wide for a line by itself is broken
+    /// between characters, as `Paragraph` does.
+    pub fn layout(&self, width: f32) -> Vec<RichLine> {
+        let mut lines = Vec::new();
+        let mut line = LineBuilder::new(self, (0, 0));
+
+        for item in self.items() {
+            match item {
+                Item::HardBreak(span, offset) => {
+                    let next = LineBuilder::new(self, (span, offset + 1));
+                    lines.push(line.finish(true, Some(span)));
+                    line = next;
+                }
+                Item::Word(atoms) => {
+                    let word_width = atoms.iter().take(atoms.len() - 1).fold(0.0, |sum, atom| sum + atom.width)
+                        + atoms[atoms.len() - 1].trimmed_width;
+                    if !line.runs.is_empty() && line.width + line.pending_space + word_width > width {
+                        let next = LineBuilder::new(self, (atoms[0].span, atoms[0].start));
+                        lines.push(line.finish(false, None));
+                        line = next;
+                    }
+                    if line.runs.is_empty() && word_width > width {
+                        line = self.break_word(atoms, width, line, &mut lines);
+                    } else {
+                        line.push_word(atoms);
+                    }
+                }
+            }
+        }
+
+        let last_span = if self.spans.is_empty() { None } else { Some(self.spans.len() - 1) };
+        lines.push(line.finish(true, last_span));
+        lines
+    }
+
+    /// Draws as many lines as fit in `rect`, starting at the top. Returns the rest of the spans, the first
+    /// of them cut where drawing stopped, or nothing if every line was drawn.
+    pub fn draw(&self, page: &mut Page, rect: Rect) -> Result<Vec<Span>> {
+        let lines = self.layout(rect.width());
+
+        let mut top = rect.top;
+        let mut drawn = 0;
+        for line in &lines {
+            if top - line.ascent + line.descent < rect.bottom - 0.001 {
+                break;
+            }
+            top -= line.ascent;
+            try!(self.draw_line(page, rect, top, line));
+            top += line.descent - self.line_gap;
+            drawn += 1;
+        }
+
+        Ok(match lines.get(drawn) {
+            Some(line) => self.spans_from(line.start),
+            None => Vec::new(),
+        })
+    }
+
+    fn draw_line(&self, page: &mut Page, rect: Rect, baseline: f32, line: &RichLine) -> Result<()> {
+        let (x, gap) = match self.align {
+            TextAlignment::Left => (rect.left, 0.0),
+            TextAlignment::Right => (rect.right - line.width, 0.0),
+            TextAlignment::Center => (rect.left + (rect.width() - line.width) / 2.0, 0.0),
+            TextAlignment::Justify => {
+                let gaps = line.runs.last().map(|run| run.gaps_before).unwrap_or(0);
+                if line.ends_paragraph || gaps == 0 {
+                    (rect.left, 0.0)
+                } else {
+                    (rect.left, (rect.width() - line.width) / gaps as f32)
+                }
+            }
+        };
+
+        try!(page.text(|text_object| {
+            for run in &line.runs {
+                let span = &self.spans[run.span];
+                try!(text_object.set_fill_color(span.color));
+                try!(text_object.set_font_and_size(&span.font, span.size));
+                try!(text_object.text_out(Point::new(x + run.x + gap * run.gaps_before as f32, baseline), &span.text[run.start..run.end]));
+            }
+            Ok( () )
+        }));
+
+        for run in &line.runs {
+            let span = &self.spans[run.span];
+            let left = x + run.x + gap * run.gaps_before as f32;
+            let thickness = span.size / 18.0;
+            if span.underline {
+                try!(page.set_fill_color(span.color));
+                try!(page.rectangle(Point::new(left, baseline - span.size / 8.0 - thickness), Size::new(run.width, thickness)));
+                try!(page.fill());
+            }
+            if span.strike {
+                let x_height = span.font.x_height();
+                let middle = if x_height > 0.0 { x_height / 2.0 } else { span.font.get_ascent() / 3.0 };
+                try!(page.set_fill_color(span.color));
+                try!(page.rectangle(Point::new(left, baseline + middle * span.size - thickness / 2.0), Size::new(run.width, thickness)));
+                try!(page.fill());
+            }
+        }
+
+        Ok( () )
+    }
+
+    /// Nowhere to break the word, so break it wherever it stops fitting. Starts on the empty `line`
+    /// and returns the line holding the end of the word.
+    fn break_word<'a>(&'a self, atoms: Vec<Atom>, width: f32, mut line: LineBuilder<'a>, lines: &mut Vec<RichLine>) -> LineBuilder<'a> {
+        let mut pieces = Vec::new();
+        let mut pieces_width = 0.0;
+        for atom in atoms {
+            let span = &self.spans[atom.span];
+            let mut start = atom.start;
+            loop {
+                let rest = &span.text[start..atom.trimmed_end];
+                let mut fit = span.font.measure(rest, span.size, width - pieces_width, 0.0, 0.0, false).bytes_fit;
+                if fit == 0 && pieces.is_empty() {
+                    fit = rest.chars().next().map(|c| c.len_utf8()).unwrap_or(rest.len());
+                }
+                if fit >= rest.len() {
+                    let piece = Atom{
+                        span: atom.span,
+                        start: start,
+                        end: atom.end,
+                        trimmed_end: atom.trimmed_end,
+                        width: width_of(span, &span.text[start..atom.end]),
+                        trimmed_width: width_of(span, rest),
+                    };
+                    pieces_width += piece.width;
+                    pieces.push(piece);
+                    break;
+                }
+
+                if fit > 0 {
+                    let piece_width = width_of(span, &rest[..fit]);
+                    pieces.push(Atom{
+                        span: atom.span,
+                        start: start,
+                        end: start + fit,
+                        trimmed_end: start + fit,
+                        width: piece_width,
+                        trimmed_width: piece_width,
+                    });
+                }
+                line.push_word(pieces.drain(..).collect());
+                pieces_width = 0.0;
+                start += fit;
+
+                let next = LineBuilder::new(self, (atom.span, start));
+                lines.push(line.finish(false, None));
+                line = next;
+            }
+        }
+        if !pieces.is_empty() {
+            line.push_word(pieces);
+        }
+        line
+    }
+
+    fn spans_from(&self, (span_index, offset): (usize, usize)) -> Vec<Span> {
+        let mut rest = Vec::new();
+        for (i, span) in self.spans.iter().enumerate().skip(span_index) {
+            let mut span = span.clone();
+            if i == span_index {
+                span.text = span.text[offset.min(span.text.len())..].to_string();
+            }
+            rest.push(span);
+        }
+        rest
+    }
+}
+
+fn width_of(span: &Span, text: &str) -> f32 {
+    if text.is_empty() {
+        0.0
+    } else {
+        span.font.measure_width(text, span.size)
+    }
+}
+
+struct LineBuilder<'a> {
+    text: &'a RichText,
+    runs: Vec<Run>,
+    width: f32,
+    pending_space: f32,
+    words: usize,
+    start: (usize, usize),
+}
+
+impl<'a> LineBuilder<'a> {
+    fn new(text: &'a RichText, start: (usize, usize)) -> LineBuilder<'a> {
+        LineBuilder{ text: text, runs: Vec::new(), width: 0.0, pending_space: 0.0, words: 0, start: start }
+    }
+
+    fn push_word(&mut self, atoms: Vec<Atom>) {
+        let mut x = self.width + self.pending_space;
+        let last = atoms.len() - 1;
+        for (i, atom) in atoms.into_iter().enumerate() {
+            let (end, width) = if i == last { (atom.trimmed_end, atom.trimmed_width) } else { (atom.end, atom.width) };
+            if i == last {
+                self.width = x + atom.trimmed_width;
+                self.pending_space = atom.width - atom.trimmed_width;
+            }
+            if end > atom.start {
+                self.runs.push(Run{
+                    span: atom.span,
+                    start: atom.start,
+                    end: end,
+                    x: x,
+                    width: width,
+                    gaps_before: self.words,
+                });
+            }
+            x += atom.width;
+        }
+        self.words += 1;
+    }
+
+    /// `metrics_span` gives the line its height when it has no runs of its own.
+    fn finish(self, ends_paragraph: bool, metrics_span: Option<usize>) -> RichLine {
+        let spans = &self.text.spans;
+        let mut ascent = 0.0f32;
+        let mut descent = 0.0f32;
+        let span_indices: Vec<usize> = if self.runs.is_empty() {
+            metrics_span.into_iter().collect()
+        } else {
+            self.runs.iter().map(|run| run.span).collect()
+        };
+        for i in span_indices {
+            let span = &spans[i];
+            ascent = ascent.max(span.font.get_ascent() * span.size);
+            descent = descent.min(span.font.get_descent() * span.size);
+        }
+
+        RichLine{
+            runs: self.runs,
+            width: self.width,
+            ascent: ascent,
+            descent: descent,
+            ends_paragraph: ends_paragraph,
+            start: self.start,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RichText, Span};
+    use base_font::BaseFont;
+    use document::Document;
+    use encoding::Encoding;
+    use page::Rect;
+
+    #[test]
+    fn mixed_sizes_share_a_baseline() {
+        let doc = Document::new().unwrap();
+        let font = doc.get_font(BaseFont::Helvetica, Encoding::WinAnsi).unwrap();
+        let text = RichText::new(vec![Span::new("small ", &font, 10.0), Span::new("Big", &font, 20.0)]);
+
+        let lines = text.layout(500.0);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].ascent, font.get_ascent() * 20.0);
+        assert_eq!(lines[0].descent, font.get_descent() * 20.0);
+        let spans: Vec<usize> = lines[0].runs.iter().map(|run| run.span).collect();
+        assert_eq!(spans, vec![0, 1]);
+        assert_eq!(lines[0].runs[1].x, font.measure_width("small ", 10.0));
+    }
+
+    #[test]
+    fn keeps_a_word_that_changes_style_on_one_line() {
+        let doc = Document::new().unwrap();
+        let regular = doc.get_font(BaseFont::Helvetica, Encoding::WinAnsi).unwrap();
+        let bold = doc.get_font(BaseFont::HelveticaBold, Encoding::WinAnsi).unwrap();
+        let text = RichText::new(vec![Span::new("hello wor", &regular, 10.0), Span::new("ld", &bold, 10.0)]);
+
+        let lines = text.layout(regular.measure_width("hello wor", 10.0) + 1.0);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].start, (0, 6));
+        let runs: Vec<(usize, usize, usize)> = lines[1].runs.iter().map(|run| (run.span, run.start, run.end)).collect();
+        assert_eq!(runs, vec![(0, 6, 9), (1, 0, 2)]);
+    }
+
+    #[test]
+    fn draws_what_fits_and_returns_the_rest() {
+        let doc = Document::new().unwrap();
+        let regular = doc.get_font(BaseFont::Helvetica, Encoding::WinAnsi).unwrap();
+        let bold = doc.get_font(BaseFont::HelveticaBold, Encoding::WinAnsi).unwrap();
+        let text = RichText::new(vec![Span::new("one two three ", &regular, 10.0), Span::new("four", &bold, 10.0)]);
+        let width = regular.measure_width("one two", 10.0) + 1.0;
+        let height = regular.line_height(10.0) * 1.5;
+
+        let mut page = doc.add_page().unwrap();
+        let rest = text.draw(&mut page, Rect::from_edges(0.0, 100.0 - height, width, 100.0)).unwrap();
+        let texts: Vec<&str> = rest.iter().map(|span| &span.text[..]).collect();
+        assert_eq!(texts, vec!["three ", "four"]);
+    }
+}
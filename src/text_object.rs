@@ -0,0 +1,154 @@
+use libharu_sys as haru;
+use std::ffi::CString;
+
+use error::{Code, Error, Result};
+use font::Font;
+use matrix::Matrix;
+use page::{Color, Page, Point, Rect, TextAlignment, TextRenderingMode};
+
+/// A text object on a page, the only place text can be shown. Created by `Page::begin_text`,
+/// and ended when dropped.
+///
+/// Only operators that are allowed inside a text object are offered here.
+pub struct TextObject<'a> {
+    handle: haru::HPDF_Page,
+    doc: haru::HPDF_Doc,
+    page: &'a mut Page,
+    ended: bool,
+}
+
+impl<'a> TextObject<'a> {
+    /// Wraps a page on which `HPDF_Page_BeginText` has just succeeded.
+    pub(crate) fn new(page: &'a mut Page) -> TextObject<'a> {
+        TextObject{ handle: unsafe { page.get_handle() }, doc: unsafe { page.get_doc_handle() }, page: page, ended: false }
+    }
+    
+    /// Ends the text object, reporting any error that dropping it would ignore.
+    pub fn end(mut self) -> Result<()> {
+        self.ended = true;
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_EndText(self.handle) } )
+    }
+    
+    /// The page, for reading its state.
+    pub fn page(&self) -> &Page {
+        &*self.page
+    }
+    
+    pub fn set_font_and_size(&mut self, font: &Font, size: f32) -> Result<()> {
+        self.page.set_font_and_size(font, size)
+    }
+    
+    pub fn set_char_space(&mut self, char_space: f32) -> Result<()> {
+        self.page.set_char_space(char_space)
+    }
+    
+    pub fn set_word_space(&mut self, word_space: f32) -> Result<()> {
+        self.page.set_word_space(word_space)
+    }
+    
+    pub fn set_horizontal_scaling(&mut self, scaling: f32) -> Result<()> {
+        self.page.set_horizontal_scaling(scaling)
+    }
+    
+    pub fn set_text_leading(&mut self, leading: f32) -> Result<()> {
+        self.page.set_text_leading(leading)
+    }
+    
+    pub fn set_text_rendering_mode(&mut self, mode: TextRenderingMode) -> Result<()> {
+        self.page.set_text_rendering_mode(mode)
+    }
+    
+    pub fn set_text_rise(&mut self, rise: f32) -> Result<()> {
+        self.page.set_text_rise(rise)
+    }
+    
+    pub fn set_fill_color(&mut self, color: Color) -> Result<()> {
+        self.page.set_fill_color(color)
+    }
+    
+    pub fn set_stroke_color(&mut self, color: Color) -> Result<()> {
+        self.page.set_stroke_color(color)
+    }
+    
+    pub fn text_out(&mut self, baseline_left: Point, text: &str) ->  Result<()> {
+        let chrs = try!(CString::new(text));
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_TextOut(self.handle, baseline_left.x, baseline_left.y, chrs.as_ptr()) } )
+    }
+    
+    /// Lays out and draws text inside `rect` using libharu's own line breaking, which only breaks at spaces.
+    /// Returns how many bytes of `text` fit; `Paragraph` offers finer control.
+    pub fn text_rect(&mut self, rect: Rect, text: &str, align: TextAlignment) -> Result<usize> {
+        let chrs = try!(CString::new(text));
+        let mut len: haru::HPDF_UINT = 0;
+        let status = unsafe { haru::HPDF_Page_TextRect(self.handle,
+            rect.left, rect.top, rect.right, rect.bottom, chrs.as_ptr(), align as haru::HPDF_TextAlignment, &mut len) };
+        // Not all of the text fitting is reported as an error, but here it is expected.
+        if Code::from_status(status) != Some(Code::PageInsufficientSpace) {
+            try!(Error::from_doc_status(self.doc, status));
+        }
+        Ok(len as usize)
+    }
+    
+    /// Like `text_out`, but for text already encoded in the current font's encoding, such as Shift-JIS
+    /// for `Encoding::Jp90msRksjH`.
+    pub fn text_out_bytes(&mut self, baseline_left: Point, text: &[u8]) ->  Result<()> {
+        let chrs = try!(CString::new(text));
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_TextOut(self.handle, baseline_left.x, baseline_left.y, chrs.as_ptr()) } )
+    }
+    
+    /// Moves to the start of the next line, offset from the start of the current line.
+    pub fn move_text_pos(&mut self, offset: Point) -> Result<()> {
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_MoveTextPos(self.handle, offset.x, offset.y) } )
+    }
+    
+    /// Like `move_text_pos`, but also sets the text leading to `-offset.y`.
+    pub fn move_text_pos_and_set_leading(&mut self, offset: Point) -> Result<()> {
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_MoveTextPos2(self.handle, offset.x, offset.y) } )
+    }
+    
+    /// Moves to the start of the next line, using the text leading.
+    pub fn move_to_next_line(&mut self) -> Result<()> {
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_MoveToNextLine(self.handle) } )
+    }
+    
+    /// Replaces the text matrix and the text line matrix.
+    pub fn set_text_matrix(&mut self, matrix: Matrix) -> Result<()> {
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_SetTextMatrix(self.handle,
+            matrix.a, matrix.b, matrix.c, matrix.d, matrix.x, matrix.y) } )
+    }
+    
+    pub fn text_matrix(&self) -> Matrix {
+        Matrix::from_haru( unsafe { haru::HPDF_Page_GetTextMatrix(self.handle) } )
+    }
+    
+    /// Shows text at the current text position.
+    pub fn show_text(&mut self, text: &str) -> Result<()> {
+        let chrs = try!(CString::new(text));
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_ShowText(self.handle, chrs.as_ptr()) } )
+    }
+    
+    /// Moves to the next line and shows text: the `'` operator.
+    pub fn show_text_next_line(&mut self, text: &str) -> Result<()> {
+        let chrs = try!(CString::new(text));
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_ShowTextNextLine(self.handle, chrs.as_ptr()) } )
+    }
+    
+    /// Sets the word and character spacing, moves to the next line and shows text: the `"` operator.
+    pub fn show_text_next_line_with_spacing(&mut self, word_space: f32, char_space: f32, text: &str) -> Result<()> {
+        let chrs = try!(CString::new(text));
+        Error::from_doc_status(self.doc, unsafe { haru::HPDF_Page_ShowTextNextLineEx(self.handle, word_space, char_space, chrs.as_ptr()) } )
+    }
+    
+    pub fn current_text_pos(&self) -> Point {
+        let pos = unsafe { haru::HPDF_Page_GetCurrentTextPos(self.handle) };
+        Point::new(pos.x, pos.y)
+    }
+}
+
+impl<'a> Drop for TextObject<'a> {
+    fn drop(&mut self) {
+        if !self.ended {
+            unsafe { haru::HPDF_Page_EndText(self.handle); }
+        }
+    }
+}
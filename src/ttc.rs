@@ -0,0 +1,256 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use error::{Code, Error, Result};
+
+/// One face in a TrueType Collection, as listed by `list_ttc_faces`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TtcFace {
+    /// The index to pass to `Document::load_ttc_font`.
+    pub index: u32,
+    
+    /// The font family name, such as "Noto Sans CJK JP".
+    pub family: Option<String>,
+    
+    /// The style within the family, such as "Bold".
+    pub style: Option<String>,
+}
+
+const NAME_ID_FAMILY: u16 = 1;
+const NAME_ID_STYLE: u16 = 2;
+
+const PLATFORM_UNICODE: u16 = 0;
+const PLATFORM_MACINTOSH: u16 = 1;
+const PLATFORM_WINDOWS: u16 = 3;
+
+const LANGUAGE_WINDOWS_EN_US: u16 = 0x409;
+
+/// Lists the faces in a TrueType Collection (.ttc) file, so that the right index can be picked
+/// for `Document::load_ttc_font`. Fails with `Code::InvalidTtcFile` if the file is cut short.
+pub fn list_ttc_faces<R: Read+Seek>(r: &mut R) -> Result<Vec<TtcFace>> {
+    match read_faces(r) {
+        Err(ref err) if err.code == Code::Io(io::ErrorKind::UnexpectedEof) => Error::new_err(Code::InvalidTtcFile),
+        result => result,
+    }
+}
+
+fn read_faces<R: Read+Seek>(r: &mut R) -> Result<Vec<TtcFace>> {
+    try!(r.seek(SeekFrom::Start(0)));
+    
+    let mut tag = [0u8; 4];
+    try!(r.read_exact(&mut tag));
+    if &tag != b"ttcf" {
+        return Error::new_err(Code::InvalidTtcFile);
+    }
+    let _version = try!(read_u32(r));
+    let num_fonts = try!(read_u32(r));
+    
+    let mut offsets = Vec::new();
+    for _ in 0..num_fonts {
+        offsets.push(try!(read_u32(r)));
+    }
+    
+    let mut faces = Vec::with_capacity(offsets.len());
+    for (index, offset) in offsets.into_iter().enumerate() {
+        let (family, style) = try!(read_face_names(r, offset as u64));
+        faces.push(TtcFace{ index: index as u32, family: family, style: style });
+    }
+    
+    Ok(faces)
+}
+
+fn read_face_names<R: Read+Seek>(r: &mut R, offset: u64) -> Result<(Option<String>, Option<String>)> {
+    try!(r.seek(SeekFrom::Start(offset + 4))); // skip the sfnt version
+    let num_tables = try!(read_u16(r));
+    try!(r.seek(SeekFrom::Current(6))); // searchRange, entrySelector, rangeShift
+    
+    let mut name_table_offset = None;
+    for _ in 0..num_tables {
+        let mut tag = [0u8; 4];
+        try!(r.read_exact(&mut tag));
+        let _checksum = try!(read_u32(r));
+        let table_offset = try!(read_u32(r));
+        let _length = try!(read_u32(r));
+        if &tag == b"name" {
+            name_table_offset = Some(table_offset as u64);
+        }
+    }
+    
+    let name_table_offset = match name_table_offset {
+        Some(offset) => offset,
+        None => { return Ok( (None, None) ); }
+    };
+    
+    try!(r.seek(SeekFrom::Start(name_table_offset)));
+    let _format = try!(read_u16(r));
+    let count = try!(read_u16(r));
+    let string_offset = try!(read_u16(r));
+    
+    let mut records = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        records.push(NameRecord{
+            platform_id: try!(read_u16(r)),
+            encoding_id: try!(read_u16(r)),
+            language_id: try!(read_u16(r)),
+            name_id: try!(read_u16(r)),
+            length: try!(read_u16(r)),
+            offset: try!(read_u16(r)),
+        });
+    }
+    
+    let strings_start = name_table_offset + string_offset as u64;
+    let family = try!(read_best_name(r, &records, NAME_ID_FAMILY, strings_start));
+    let style = try!(read_best_name(r, &records, NAME_ID_STYLE, strings_start));
+    Ok( (family, style) )
+}
+
+struct NameRecord {
+    platform_id: u16,
+    encoding_id: u16,
+    language_id: u16,
+    name_id: u16,
+    length: u16,
+    offset: u16,
+}
+
+impl NameRecord {
+    /// How much to prefer this record over others with the same name ID, or `None` if it can't be decoded.
+    fn preference(&self) -> Option<u32> {
+        match (self.platform_id, self.encoding_id) {
+            (PLATFORM_WINDOWS, 0) | (PLATFORM_WINDOWS, 1) | (PLATFORM_WINDOWS, 10) => {
+                Some(if self.language_id == LANGUAGE_WINDOWS_EN_US { 4 } else { 3 })
+            }
+            (PLATFORM_UNICODE, _) => Some(2),
+            (PLATFORM_MACINTOSH, 0) => Some(1),
+            _ => None,
+        }
+    }
+    
+    fn decode(&self, bytes: &[u8]) -> String {
+        if self.platform_id == PLATFORM_MACINTOSH {
+            // Mac Roman agrees with Latin-1 for the ASCII range that font names almost always use.
+            bytes.iter().map(|&b| b as char).collect()
+        } else {
+            let units: Vec<u16> = bytes.chunks(2)
+                .filter(|pair| pair.len() == 2)
+                .map(|pair| ((pair[0] as u16) << 8) | pair[1] as u16)
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+    }
+}
+
+fn read_best_name<R: Read+Seek>(r: &mut R, records: &[NameRecord], name_id: u16, strings_start: u64) -> Result<Option<String>> {
+    let best = records.iter()
+        .filter(|record| record.name_id == name_id)
+        .filter_map(|record| record.preference().map(|preference| (preference, record)))
+        .max_by_key(|&(preference, _)| preference);
+    
+    let record = match best {
+        Some((_, record)) => record,
+        None => { return Ok(None); }
+    };
+    
+    let mut bytes = vec![0u8; record.length as usize];
+    try!(r.seek(SeekFrom::Start(strings_start + record.offset as u64)));
+    try!(r.read_exact(&mut bytes));
+    Ok(Some(record.decode(&bytes)))
+}
+
+fn read_u16<R: Read>(r: &mut R) -> Result<u16> {
+    let mut buf = [0u8; 2];
+    try!(r.read_exact(&mut buf));
+    Ok(((buf[0] as u16) << 8) | buf[1] as u16)
+}
+
+fn read_u32<R: Read>(r: &mut R) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    try!(r.read_exact(&mut buf));
+    Ok(((buf[0] as u32) << 24) | ((buf[1] as u32) << 16) | ((buf[2] as u32) << 8) | buf[3] as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    
+    fn push_u16(buf: &mut Vec<u8>, v: u16) {
+        buf.push((v >> 8) as u8);
+        buf.push(v as u8);
+    }
+    
+    fn push_u32(buf: &mut Vec<u8>, v: u32) {
+        push_u16(buf, (v >> 16) as u16);
+        push_u16(buf, v as u16);
+    }
+    
+    /// A face with only a name table, holding a Windows family name and a Mac style name.
+    fn face(buf: &mut Vec<u8>, family: &str, style: &str) {
+        let start = buf.len() as u32;
+        push_u32(buf, 0x00010000);
+        push_u16(buf, 1);
+        push_u16(buf, 0);
+        push_u16(buf, 0);
+        push_u16(buf, 0);
+        buf.extend_from_slice(b"name");
+        push_u32(buf, 0);
+        push_u32(buf, start + 12 + 16);
+        push_u32(buf, 0);
+        
+        let family_utf16: Vec<u16> = family.encode_utf16().collect();
+        push_u16(buf, 0);
+        push_u16(buf, 2);
+        push_u16(buf, 6 + 2 * 12);
+        for &(platform, encoding, language, name_id, length, offset) in &[
+            (PLATFORM_WINDOWS, 1, LANGUAGE_WINDOWS_EN_US, NAME_ID_FAMILY, family_utf16.len() as u16 * 2, 0),
+            (PLATFORM_MACINTOSH, 0, 0, NAME_ID_STYLE, style.len() as u16, family_utf16.len() as u16 * 2),
+        ] {
+            for &v in &[platform, encoding, language, name_id, length, offset] {
+                push_u16(buf, v);
+            }
+        }
+        for unit in family_utf16 {
+            push_u16(buf, unit);
+        }
+        buf.extend_from_slice(style.as_bytes());
+    }
+    
+    #[test]
+    fn lists_faces() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"ttcf");
+        push_u32(&mut buf, 0x00010000);
+        push_u32(&mut buf, 2);
+        push_u32(&mut buf, 0);
+        push_u32(&mut buf, 0);
+        
+        let first = buf.len() as u32;
+        face(&mut buf, "Noto Sans CJK JP", "Regular");
+        let second = buf.len() as u32;
+        face(&mut buf, "Noto Sans CJK KR", "Bold");
+        buf[12..16].copy_from_slice(&[(first >> 24) as u8, (first >> 16) as u8, (first >> 8) as u8, first as u8]);
+        buf[16..20].copy_from_slice(&[(second >> 24) as u8, (second >> 16) as u8, (second >> 8) as u8, second as u8]);
+        
+        let faces = list_ttc_faces(&mut Cursor::new(buf)).unwrap();
+        assert_eq!(faces, vec![
+            TtcFace{ index: 0, family: Some("Noto Sans CJK JP".to_string()), style: Some("Regular".to_string()) },
+            TtcFace{ index: 1, family: Some("Noto Sans CJK KR".to_string()), style: Some("Bold".to_string()) },
+        ]);
+    }
+    
+    #[test]
+    fn rejects_plain_ttf() {
+        let mut buf = Vec::new();
+        face(&mut buf, "Roboto", "Regular");
+        assert_eq!(list_ttc_faces(&mut Cursor::new(buf)).unwrap_err().code, Code::InvalidTtcFile);
+    }
+    
+    #[test]
+    fn rejects_truncated_header() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"ttcf");
+        push_u32(&mut buf, 0x00010000);
+        push_u32(&mut buf, 2);
+        push_u32(&mut buf, 20);
+        assert_eq!(list_ttc_faces(&mut Cursor::new(buf)).unwrap_err().code, Code::InvalidTtcFile);
+    }
+}